@@ -0,0 +1,58 @@
+//! Move generation throughput, run with `cargo bench --bench movegen`.
+
+use std::time::{Duration, Instant};
+
+use chinese_checkers::board::{Board, HexCoord};
+
+const MEASURE_FOR: Duration = Duration::from_secs(2);
+const PLIES: usize = 60;
+
+/// The positions reached by playing `PLIES` moves from the start, always
+/// taking the move that lands furthest "down" the board so the sequence
+/// doesn't depend on iteration order.
+fn positions(players_count: usize) -> Vec<Board> {
+    let mut board = Board::new(players_count).unwrap();
+    let mut positions = vec![board.clone()];
+    for ply in 0..PLIES {
+        let key = |&(start, end): &(HexCoord, HexCoord)| {
+            let direction = if ply % 2 == 0 { 1 } else { -1 };
+            (
+                direction * (end.slant - start.slant),
+                end.horz,
+                start.horz,
+                start.slant,
+            )
+        };
+        match board.legal_moves().into_iter().max_by_key(key) {
+            Some((start, end)) => board.make_move(start, end),
+            None => break,
+        }
+        positions.push(board.clone());
+    }
+    positions
+}
+
+fn main() {
+    println!("players  positions/s  moves/s");
+    for &players_count in &[2, 3, 4, 6] {
+        let positions = positions(players_count);
+
+        let start = Instant::now();
+        let mut generated = 0;
+        let mut iterations = 0;
+        while start.elapsed() < MEASURE_FOR {
+            for board in &positions {
+                generated += board.legal_moves().len();
+            }
+            iterations += positions.len();
+        }
+        let seconds = start.elapsed().as_secs_f64();
+
+        println!(
+            "{:>7}  {:>11.0}  {:>7.0}",
+            players_count,
+            iterations as f64 / seconds,
+            generated as f64 / seconds
+        );
+    }
+}
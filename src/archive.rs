@@ -0,0 +1,292 @@
+//! Every finished game, kept in a JSON file so it can be looked back on and
+//! replayed.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::board::{
+    AntiSpoiling, Board, Draw, DrawRules, Move, ParsePositionError, Player, PositionError, Seat,
+    SetupError, SideOfStar, TurnOrder,
+};
+use crate::storage::{load_json, save_json};
+
+pub const ARCHIVE_PATH: &str = "games.json";
+
+/// Everything about how a game was set up.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Variant {
+    pub goals: BTreeMap<Player, SideOfStar>,
+    pub seats: Vec<Seat>,
+    pub turn_order: TurnOrder,
+    pub team_play: bool,
+    pub rules: AntiSpoiling,
+    pub draw_rules: DrawRules,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchivedGame {
+    /// seconds since the Unix epoch
+    pub finished_at: u64,
+    pub duration: Duration,
+    pub players: BTreeSet<Player>,
+    /// the profile playing each side, guests having none
+    pub profiles: BTreeMap<Player, String>,
+    pub variant: Variant,
+    /// the position string of the board the game started from, when it was
+    /// set up in the editor instead of starting as usual
+    #[serde(default)]
+    pub start_position: Option<String>,
+    pub moves: Vec<Move>,
+    /// how many jumps each move chained together, 0 for a single step
+    pub jumps: Vec<usize>,
+    pub team_ranking: Vec<BTreeSet<Player>>,
+    pub draw: Option<Draw>,
+}
+
+impl ArchivedGame {
+    /// Archives the game on `board`, played with `rules` for `duration`.
+    /// `start_position` is the board it started from, if it was set up in
+    /// the editor.
+    pub fn new(
+        board: &Board,
+        start_position: Option<&Board>,
+        rules: AntiSpoiling,
+        profiles: &BTreeMap<Player, String>,
+        duration: Duration,
+    ) -> Self {
+        let mut game = Self {
+            finished_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since_epoch| since_epoch.as_secs()),
+            duration,
+            players: board.players.clone(),
+            profiles: profiles
+                .iter()
+                .filter(|(side, _name)| board.players.contains(side))
+                .map(|(&side, name)| (side, name.clone()))
+                .collect(),
+            variant: Variant {
+                goals: board.goals.clone(),
+                seats: board.seats.clone(),
+                turn_order: board.turn_order,
+                team_play: board.team_play,
+                rules,
+                draw_rules: board.draw_rules,
+            },
+            start_position: start_position.map(Board::to_position_string),
+            moves: board.history.clone(),
+            jumps: Vec::new(),
+            team_ranking: board.outcome.team_ranking.clone(),
+            draw: board.outcome.draw,
+        };
+
+        if let Ok(mut replay) = game.start_board() {
+            for played in &game.moves {
+                game.jumps.push(replay.jump_count(played.start, played.end));
+                replay.make_move(played.start, played.end);
+            }
+        }
+        game
+    }
+
+    /// The board as the game started.
+    pub fn start_board(&self) -> Result<Board, ReplayError> {
+        let variant = &self.variant;
+        let mut board = match &self.start_position {
+            Some(position) => {
+                let mut board = Board::from_position_string(position)?;
+                board.set_rules(variant.rules);
+                board
+            }
+            None => Board::with_players(self.players.clone(), variant.rules)?,
+        };
+        for (&player, &goal) in &variant.goals {
+            board.set_goal(player, goal);
+        }
+        if !variant.seats.is_empty() {
+            board.set_seats(variant.seats.clone());
+        }
+        board.turn_order = variant.turn_order;
+        board.team_play = variant.team_play;
+        board.draw_rules = variant.draw_rules;
+        match self.start_position {
+            Some(_) => board.start_from_position()?,
+            None => board.setup_players()?,
+        }
+        Ok(board)
+    }
+
+    /// The board after the first `moves` moves.
+    pub fn board_after(&self, moves: usize) -> Result<Board, ReplayError> {
+        let mut board = self.start_board()?;
+        for played in self.moves.iter().take(moves) {
+            board.make_move(played.start, played.end);
+        }
+        Ok(board)
+    }
+
+    /// The profile name playing `side`, or the side for guests.
+    pub fn player_name(&self, side: Player) -> String {
+        match self.profiles.get(&side) {
+            Some(name) => name.clone(),
+            None => format!("Side {:?}", side),
+        }
+    }
+
+    pub fn winners(&self) -> Option<&BTreeSet<Player>> {
+        self.team_ranking.first()
+    }
+
+    /// The most jumps `side` chained together in one move.
+    pub fn longest_jump(&self, side: Player) -> usize {
+        self.moves
+            .iter()
+            .zip(&self.jumps)
+            .filter(|(played, _jumps)| played.player == side)
+            .map(|(_played, &jumps)| jumps)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// When the game finished, as `YYYY-MM-DD HH:MM` in UTC.
+    pub fn finished_date(&self) -> String {
+        let days = (self.finished_at / 86_400) as i64;
+        let minutes = self.finished_at % 86_400 / 60;
+        // Howard Hinnant's civil_from_days
+        let era_days = days + 719_468;
+        let era = era_days.div_euclid(146_097);
+        let day_of_era = era_days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        format!(
+            "{:04}-{:02}-{:02} {:02}:{:02}",
+            year,
+            month,
+            day,
+            minutes / 60,
+            minutes % 60
+        )
+    }
+}
+
+/// Why an archived game can't be set up again to replay it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayError {
+    Setup(SetupError),
+    /// the start position string doesn't parse
+    Parse(ParsePositionError),
+    /// the start position isn't one a game can start from
+    Position(PositionError),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Setup(error) => error.fmt(f),
+            Self::Parse(error) => write!(f, "bad start position: {}", error),
+            Self::Position(error) => write!(f, "bad start position: {}", error),
+        }
+    }
+}
+
+impl Error for ReplayError {}
+
+impl From<SetupError> for ReplayError {
+    fn from(error: SetupError) -> Self {
+        Self::Setup(error)
+    }
+}
+
+impl From<ParsePositionError> for ReplayError {
+    fn from(error: ParsePositionError) -> Self {
+        Self::Parse(error)
+    }
+}
+
+impl From<PositionError> for ReplayError {
+    fn from(error: PositionError) -> Self {
+        Self::Position(error)
+    }
+}
+
+/// How a player did over a number of games.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PlayerStats {
+    pub games: usize,
+    pub wins: usize,
+    pub moves: usize,
+    /// the longest jump chain of every game, summed up
+    pub longest_jumps: usize,
+}
+
+impl PlayerStats {
+    pub fn average_moves(&self) -> f32 {
+        self.moves as f32 / self.games.max(1) as f32
+    }
+
+    pub fn average_longest_jump(&self) -> f32 {
+        self.longest_jumps as f32 / self.games.max(1) as f32
+    }
+}
+
+/// The stats of every player in `games`, by `ArchivedGame::player_name`.
+/// Games started from a position set up in the editor don't count, like for
+/// the ratings.
+pub fn player_stats<'a>(
+    games: impl IntoIterator<Item = &'a ArchivedGame>,
+) -> BTreeMap<String, PlayerStats> {
+    let mut stats: BTreeMap<String, PlayerStats> = BTreeMap::new();
+    for game in games {
+        if game.start_position.is_some() {
+            continue;
+        }
+        for &side in &game.players {
+            let player_stats = stats.entry(game.player_name(side)).or_default();
+            player_stats.games += 1;
+            if game
+                .winners()
+                .is_some_and(|winners| winners.contains(&side))
+            {
+                player_stats.wins += 1;
+            }
+            player_stats.moves += game
+                .moves
+                .iter()
+                .filter(|played| played.player == side)
+                .count();
+            player_stats.longest_jumps += game.longest_jump(side);
+        }
+    }
+    stats
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Archive {
+    /// oldest first
+    pub games: Vec<ArchivedGame>,
+}
+
+impl Archive {
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        load_json(path)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        save_json(self, path)
+    }
+}
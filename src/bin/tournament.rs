@@ -0,0 +1,331 @@
+//! Plays bots against each other without the GUI and reports how they did
+//! as CSV on stdout.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use chinese_checkers::board::{Board, Player};
+use chinese_checkers::engine::BotKind;
+
+const USAGE: &str = "\
+usage: tournament [options]
+    --games <n>          games per player count (default 12); with n bots
+                         every pairing meets with both seatings after
+                         n*(n-1) games
+    --bots <list>        comma separated bots, each one of random, greedy,
+                         minimax:<depth> or mcts:<playouts>
+                         (default greedy,minimax:1,minimax:2,mcts:200)
+    --players <list>     comma separated player counts from 2 to 6
+                         (default 2,3,4,5,6)
+    --threads <n>        games played at once (default: one per core)
+    --max-moves <n>      moves after which a game counts as a draw (default 600)
+    --seed <n>           seed for the bots' random choices (default 0)";
+
+/// the rating every bot's rating is relative to
+const MEAN_ELO: f64 = 1500.0;
+const ELO_FIT_ROUNDS: usize = 200;
+
+struct Options {
+    games: usize,
+    bots: Vec<BotKind>,
+    players_counts: Vec<usize>,
+    threads: usize,
+    max_moves: usize,
+    seed: u64,
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut options = Self {
+            games: 12,
+            bots: parse_list("bots", "greedy,minimax:1,minimax:2,mcts:200")?,
+            players_counts: parse_list("players", "2,3,4,5,6")?,
+            threads: thread::available_parallelism().map_or(1, |threads| threads.get()),
+            max_moves: 600,
+            seed: 0,
+        };
+
+        for pair in args.chunks(2) {
+            let (flag, value) = match pair {
+                [flag, value] => (flag.as_str(), value.as_str()),
+                _ => return Err(USAGE.to_string()),
+            };
+            match flag {
+                "--games" => options.games = parse_value("games", value)?,
+                "--bots" => options.bots = parse_list("bots", value)?,
+                "--players" => options.players_counts = parse_list("players", value)?,
+                "--threads" => options.threads = parse_value("threads", value)?,
+                "--max-moves" => options.max_moves = parse_value("max-moves", value)?,
+                "--seed" => options.seed = parse_value("seed", value)?,
+                _ => return Err(USAGE.to_string()),
+            }
+        }
+
+        if options.bots.is_empty() || options.threads == 0 {
+            return Err(USAGE.to_string());
+        }
+        if let Some(&count) = options
+            .players_counts
+            .iter()
+            .find(|&&count| !(2..=6).contains(&count))
+        {
+            return Err(format!("can't play with {} players\n{}", count, USAGE));
+        }
+        Ok(options)
+    }
+}
+
+fn parse_value<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("bad --{} {:?}\n{}", name, value, USAGE))
+}
+
+fn parse_list<T: std::str::FromStr>(name: &str, values: &str) -> Result<Vec<T>, String> {
+    values
+        .split(',')
+        .map(|value| parse_value(name, value.trim()))
+        .collect()
+}
+
+/// How one game went.
+struct GameRecord {
+    players_count: usize,
+    /// the index into `Options::bots` playing each side
+    lineup: BTreeMap<Player, usize>,
+    /// see `GameOutcome::places`
+    places: BTreeMap<Player, usize>,
+    winners: Vec<Player>,
+    moves: usize,
+}
+
+/// The bots playing each seat, game after game, as indices into
+/// `Options::bots`. Every two bots meet with the seats split between them,
+/// first one way round for every pair and then rotated a seat further, so
+/// that each pairing has every bot in every seat and moving first.
+fn lineups(bots: usize, seats: usize) -> Vec<Vec<usize>> {
+    if bots < 2 {
+        return vec![vec![0; seats]];
+    }
+    let pairs: Vec<(usize, usize)> = (0..bots)
+        .flat_map(|first| (0..bots).map(move |second| (first, second)))
+        .filter(|(first, second)| first != second)
+        .collect();
+    let mut lineups: Vec<Vec<usize>> = Vec::new();
+    for rotation in 0..seats {
+        for &(first, second) in &pairs {
+            let lineup: Vec<usize> = (0..seats)
+                .map(|seat| match (seat + rotation) % seats % 2 {
+                    0 => first,
+                    _ => second,
+                })
+                .collect();
+            if !lineups.contains(&lineup) {
+                lineups.push(lineup);
+            }
+        }
+    }
+    lineups
+}
+
+/// Plays game `game` for `players_count` players, with the bots of the
+/// `game`th of the `lineups`.
+fn play_game(options: &Options, players_count: usize, game: usize) -> GameRecord {
+    let mut board = Board::new(players_count).expect("player counts are checked up front");
+    let lineups = lineups(options.bots.len(), players_count);
+    let lineup: BTreeMap<Player, usize> = board
+        .players
+        .iter()
+        .copied()
+        .zip(lineups[game % lineups.len()].iter().copied())
+        .collect();
+    let mut bots: BTreeMap<Player, _> = lineup
+        .iter()
+        .map(|(&player, &bot)| {
+            let seed = options.seed
+                ^ ((players_count * 1_000_003 + game) as u64) << 3
+                ^ player.index() as u64;
+            (player, options.bots[bot].build(seed))
+        })
+        .collect();
+
+    while !board.is_over() && board.history.len() < options.max_moves {
+        let (start, end) = match bots
+            .get_mut(&board.turn)
+            .and_then(|bot| bot.choose_move(&board))
+        {
+            Some(chosen) => chosen,
+            None => break,
+        };
+        let moves_made = board.history.len();
+        board.make_move(start, end);
+        if board.history.len() == moves_made {
+            break;
+        }
+    }
+
+    let outcome = &board.outcome;
+    let winners = outcome
+        .winning_team()
+        .map(|team| team.iter().copied().collect())
+        .unwrap_or_default();
+
+    GameRecord {
+        players_count,
+        lineup,
+        places: outcome.places(&board.players),
+        winners,
+        moves: board.history.len(),
+    }
+}
+
+fn play_all(options: &Options) -> Vec<GameRecord> {
+    let jobs: Vec<(usize, usize)> = options
+        .players_counts
+        .iter()
+        .flat_map(|&count| (0..options.games).map(move |game| (count, game)))
+        .collect();
+    let next_job = AtomicUsize::new(0);
+    let records = Mutex::new(Vec::new());
+
+    thread::scope(|scope| {
+        for _ in 0..options.threads.min(jobs.len()) {
+            scope.spawn(|| {
+                while let Some(&(players_count, game)) =
+                    jobs.get(next_job.fetch_add(1, Ordering::Relaxed))
+                {
+                    let record = play_game(options, players_count, game);
+                    eprintln!(
+                        "{} players, game {}: {} moves",
+                        players_count, game, record.moves
+                    );
+                    records.lock().unwrap().push((players_count, game, record));
+                }
+            });
+        }
+    });
+
+    let mut records = records.into_inner().unwrap();
+    records.sort_by_key(|&(players_count, game, _)| (players_count, game));
+    records.into_iter().map(|(_, _, record)| record).collect()
+}
+
+/// Elo ratings fitted to the head to head results in `records`: every pair
+/// of sides in a game counts as a win for the better placed one. Every bot
+/// also gets one drawn game against every other one, so a bot that always
+/// wins still has a finite rating.
+fn elo_ratings(bots: usize, records: &[&GameRecord]) -> Vec<f64> {
+    let mut scores = vec![vec![0.5; bots]; bots];
+    let mut games = vec![vec![1.0; bots]; bots];
+    for bot in 0..bots {
+        scores[bot][bot] = 0.0;
+        games[bot][bot] = 0.0;
+    }
+    for record in records {
+        for (first, &first_bot) in &record.lineup {
+            for (second, &second_bot) in &record.lineup {
+                if first_bot == second_bot {
+                    continue;
+                }
+                games[first_bot][second_bot] += 1.0;
+                scores[first_bot][second_bot] +=
+                    match record.places[first].cmp(&record.places[second]) {
+                        std::cmp::Ordering::Less => 1.0,
+                        std::cmp::Ordering::Equal => 0.5,
+                        std::cmp::Ordering::Greater => 0.0,
+                    };
+            }
+        }
+    }
+
+    // Bradley-Terry strengths by the minorization-maximization updates
+    let mut strengths = vec![1.0; bots];
+    for _ in 0..ELO_FIT_ROUNDS {
+        strengths = (0..bots)
+            .map(|bot| {
+                let total_score: f64 = scores[bot].iter().sum();
+                let weight: f64 = (0..bots)
+                    .map(|other| games[bot][other] / (strengths[bot] + strengths[other]))
+                    .sum();
+                if weight > 0.0 {
+                    total_score / weight
+                } else {
+                    strengths[bot]
+                }
+            })
+            .collect();
+    }
+
+    let elos: Vec<f64> = strengths
+        .iter()
+        .map(|strength| 400.0 * strength.log10())
+        .collect();
+    let mean = elos.iter().sum::<f64>() / bots as f64;
+    elos.iter().map(|elo| elo - mean + MEAN_ELO).collect()
+}
+
+fn print_csv(options: &Options, records: &[GameRecord]) {
+    println!("players,bot,games,wins,draws,win_rate,average_moves,elo");
+    let groups = options
+        .players_counts
+        .iter()
+        .map(|&count| (count.to_string(), Some(count)))
+        .chain(std::iter::once((String::from("all"), None)));
+    for (label, players_count) in groups {
+        let records: Vec<&GameRecord> = records
+            .iter()
+            .filter(|record| players_count.is_none_or(|count| record.players_count == count))
+            .collect();
+        let elos = elo_ratings(options.bots.len(), &records);
+
+        for (bot, kind) in options.bots.iter().enumerate() {
+            let played: Vec<&&GameRecord> = records
+                .iter()
+                .filter(|record| record.lineup.values().any(|&seat_bot| seat_bot == bot))
+                .collect();
+            let wins = played
+                .iter()
+                .filter(|record| {
+                    record
+                        .winners
+                        .iter()
+                        .any(|winner| record.lineup[winner] == bot)
+                })
+                .count();
+            let draws = played
+                .iter()
+                .filter(|record| record.winners.is_empty())
+                .count();
+            let games = played.len();
+            let average_moves = played.iter().map(|record| record.moves).sum::<usize>() as f64
+                / games.max(1) as f64;
+            println!(
+                "{},{},{},{},{},{:.3},{:.1},{:.0}",
+                label,
+                kind,
+                games,
+                wins,
+                draws,
+                wins as f64 / games.max(1) as f64,
+                average_moves,
+                elos[bot]
+            );
+        }
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let options = match Options::parse(&args) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}", message);
+            std::process::exit(2);
+        }
+    };
+
+    let records = play_all(&options);
+    print_csv(&options, &records);
+}
@@ -1,8 +1,33 @@
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
 use std::ops;
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+use std::sync::Arc;
+
+use maplit::btreeset;
+use serde::{Deserialize, Serialize};
+
+pub mod bitboard;
+mod draw;
+mod perft;
+mod position;
+mod rules;
+mod seats;
+mod setup;
+mod zobrist;
+pub use draw::{Draw, DrawRules};
+pub use position::{ParsePositionError, PositionError, PIECES_PER_SIDE};
+pub use rules::{AntiSpoiling, RuleSet, StandardRules};
+pub use seats::{gen_seats, single_side_seats, Seat, SeatKind, TurnOrder};
+pub use setup::SetupError;
+pub(crate) use zobrist::splitmix64;
+
+use bitboard::{geometry, Bitset, HOLES};
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize,
+)]
 pub enum SideOfStar {
+    #[default]
     A,
     B,
     C,
@@ -26,6 +51,10 @@ impl SideOfStar {
         }
     }
 
+    pub fn backward(self) -> Self {
+        self.forward().forward().forward().forward().forward()
+    }
+
     pub fn opposite(self) -> Self {
         use SideOfStar::*;
         match self {
@@ -42,16 +71,29 @@ impl SideOfStar {
         use SideOfStar::*;
         vec![A, B, C, D, E, F]
     }
-}
 
-impl Default for SideOfStar {
-    fn default() -> Self {
-        Self::A
+    /// A number from 0 to 5, used to index per side tables.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// The triangle of holes in which this side's pieces start.
+    pub fn home(self) -> Vec<HexCoord> {
+        use SideOfStar::*;
+        match self {
+            A => HexCoord::new(4, -8).triangle_tip_up(4),
+            B => HexCoord::new(5, -1).triangle_tip_down(4),
+            C => HexCoord::new(4, 1).triangle_tip_up(4),
+            D => HexCoord::new(-4, 8).triangle_tip_down(4),
+            E => HexCoord::new(-5, 1).triangle_tip_up(4),
+            F => HexCoord::new(-4, -1).triangle_tip_down(4),
+        }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum Spot {
+    #[default]
     Empty,
     Player(Player),
 }
@@ -69,12 +111,6 @@ impl Spot {
     }
 }
 
-impl Default for Spot {
-    fn default() -> Self {
-        Self::Empty
-    }
-}
-
 impl PartialEq<Player> for Spot {
     fn eq(&self, other: &Player) -> bool {
         match self {
@@ -90,7 +126,7 @@ impl PartialEq<Spot> for Player {
     }
 }
 
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct HexCoord {
     /// the horizontal axis
     pub horz: i32,
@@ -98,6 +134,12 @@ pub struct HexCoord {
     pub slant: i32,
 }
 
+impl fmt::Display for HexCoord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.horz, self.slant)
+    }
+}
+
 impl From<(i32, i32)> for HexCoord {
     fn from(other: (i32, i32)) -> Self {
         Self::new(other.0, other.1)
@@ -163,6 +205,12 @@ impl HexCoord {
             .collect()
     }
 
+    /// The number of single steps between the two holes.
+    pub fn distance(self, other: Self) -> i32 {
+        let diff = self - other;
+        (diff.horz.abs() + diff.slant.abs() + (diff.horz + diff.slant).abs()) / 2
+    }
+
     fn triangle_tip_up(self, size: i32) -> Vec<Self> {
         (0_i32..size)
             .map(|offset| (offset, self.slant + offset))
@@ -188,154 +236,540 @@ impl HexCoord {
     }
 }
 
-#[derive(Debug, Clone, Default)]
-struct GameOutcome(Vec<Player>);
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GameOutcome {
+    /// the players that finished, winner first
+    pub ranking: Vec<Player>,
+    /// the teams whose members have all finished, winners first
+    pub team_ranking: Vec<BTreeSet<Player>>,
+    /// set when the game ended without everybody finishing
+    pub draw: Option<Draw>,
+}
+
+impl GameOutcome {
+    pub fn winner(&self) -> Option<Player> {
+        self.ranking.first().copied()
+    }
+
+    pub fn winning_team(&self) -> Option<&BTreeSet<Player>> {
+        self.team_ranking.first()
+    }
+
+    pub fn has_finished(&self, player: Player) -> bool {
+        self.ranking.contains(&player)
+    }
+
+    /// The place of each of `players`, 0 for the winning team, with everyone
+    /// yet to finish sharing the last place.
+    pub fn places(&self, players: &BTreeSet<Player>) -> BTreeMap<Player, usize> {
+        let mut places: BTreeMap<Player, usize> = BTreeMap::new();
+        for (place, team) in self.team_ranking.iter().enumerate() {
+            places.extend(team.iter().map(|&player| (player, place)));
+        }
+        let last_place = self.team_ranking.len();
+        for &player in players {
+            places.entry(player).or_insert(last_place);
+        }
+        places
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Move {
+    pub player: Player,
+    pub start: HexCoord,
+    pub end: HexCoord,
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: {} -> {}", self.player, self.start, self.end)
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct Board {
-    pub board: HashMap<HexCoord, Spot>,
+    /// the spot in every hole, by `bitboard` index
+    spots: [Spot; HOLES],
+    occupied: Bitset,
+    /// the holes taken by each side's pieces, by `SideOfStar::index`
+    pieces: [Bitset; 6],
+    /// the Zobrist hash of the pieces, kept up to date by `set_spot`
+    piece_hash: u64,
+    /// the `hash` of every position so far, to spot repetitions
+    position_hashes: Vec<u64>,
     pub players: BTreeSet<Player>,
     pub turn: Player,
+    /// goal triangles that differ from the `opposite` side
+    pub goals: BTreeMap<Player, SideOfStar>,
+    pub seats: Vec<Seat>,
+    pub turn_order: TurnOrder,
+    /// whether partners share a team, see `gen_teams`
+    pub team_play: bool,
+    pub teams: Vec<BTreeSet<Player>>,
+    pub draw_rules: DrawRules,
+    progress: draw::Progress,
+    pub outcome: GameOutcome,
+    pub history: Vec<Move>,
+    rules: Arc<dyn RuleSet>,
 }
 
 impl Board {
-    pub fn new(players_count: usize) -> Self {
+    pub fn new(players_count: usize) -> Result<Self, SetupError> {
+        Self::with_rules(players_count, StandardRules)
+    }
+
+    pub fn with_rules<R: RuleSet + 'static>(
+        players_count: usize,
+        rules: R,
+    ) -> Result<Self, SetupError> {
+        let players = rules.players(players_count)?;
+        Self::with_players(players, rules)
+    }
+
+    /// A board for any non-empty set of sides.
+    pub fn with_players<R: RuleSet + 'static>(
+        players: BTreeSet<Player>,
+        rules: R,
+    ) -> Result<Self, SetupError> {
         let mut new_board = Self {
-            players: gen_players(players_count),
-            board: gen_empty_board(),
+            players,
+            spots: [Spot::Empty; HOLES],
+            occupied: Bitset::EMPTY,
+            pieces: [Bitset::EMPTY; 6],
+            piece_hash: 0,
+            position_hashes: Vec::new(),
             turn: Player::default(),
+            goals: BTreeMap::new(),
+            seats: Vec::new(),
+            turn_order: TurnOrder::default(),
+            team_play: false,
+            teams: Vec::new(),
+            draw_rules: DrawRules::default(),
+            progress: draw::Progress::default(),
+            outcome: GameOutcome::default(),
+            history: Vec::new(),
+            rules: Arc::new(rules),
         };
 
-        new_board.setup_players();
+        new_board.setup_players()?;
 
-        new_board
+        Ok(new_board)
     }
 
-    pub fn setup_players(&mut self) {
-        let player_tip = |side_of_star: SideOfStar| -> Vec<HexCoord> {
-            use SideOfStar::*;
-            match side_of_star {
-                A => HexCoord::new(4, -8).triangle_tip_up(4),
-                B => HexCoord::new(5, -1).triangle_tip_down(4),
-                C => HexCoord::new(4, 1).triangle_tip_up(4),
-                D => HexCoord::new(-4, 8).triangle_tip_down(4),
-                E => HexCoord::new(-5, 1).triangle_tip_up(4),
-                F => HexCoord::new(-4, -1).triangle_tip_down(4),
-            }
-        };
+    pub fn rules(&self) -> &dyn RuleSet {
+        self.rules.as_ref()
+    }
 
-        for (_coord, spot) in self.board.iter_mut() {
-            *spot = Spot::default()
-        }
+    /// Replaces the rules, taking effect from the next `setup_players`.
+    pub fn set_rules<R: RuleSet + 'static>(&mut self, rules: R) {
+        self.rules = Arc::new(rules);
+    }
 
-        for player in Player::all() {
-            let player_tip = player_tip(player);
-            for player_tip_coord in player_tip {
-                if self.players.contains(&player) {
-                    self.put_player(player_tip_coord, player);
-                }
-            }
+    /// Seats `seats` at the board, replacing the players with the sides they
+    /// own. Takes effect from the next `setup_players`.
+    pub fn set_seats(&mut self, seats: Vec<Seat>) {
+        self.players = seats
+            .iter()
+            .flat_map(|seat| seat.sides.iter().copied())
+            .collect();
+        self.seats = seats;
+    }
+
+    pub fn seat_of(&self, player: Player) -> Option<&Seat> {
+        self.seats.iter().find(|seat| seat.owns(player))
+    }
+
+    /// The side whose home triangle `player` has to fill.
+    pub fn goal_side(&self, player: Player) -> SideOfStar {
+        self.goals
+            .get(&player)
+            .copied()
+            .unwrap_or_else(|| player.opposite())
+    }
+
+    /// The holes `player` has to fill to finish.
+    pub fn goal(&self, player: Player) -> Vec<HexCoord> {
+        self.goal_side(player).home()
+    }
+
+    pub fn set_goal(&mut self, player: Player, goal: SideOfStar) {
+        if goal == player.opposite() {
+            self.goals.remove(&player);
+        } else {
+            self.goals.insert(player, goal);
         }
+    }
+
+    /// Whether the seats match the players and some seat owns several sides.
+    fn has_multi_side_seats(&self) -> bool {
+        let seated_sides: BTreeSet<Player> = self
+            .seats
+            .iter()
+            .flat_map(|seat| seat.sides.iter().copied())
+            .collect();
+        seated_sides == self.players && self.seats.iter().any(|seat| seat.sides.len() > 1)
+    }
 
-        self.turn = self.players.iter().next().copied().unwrap();
+    pub fn setup_players(&mut self) -> Result<(), SetupError> {
+        self.validate_setup()?;
+
+        self.spots = [Spot::Empty; HOLES];
+        self.occupied = Bitset::EMPTY;
+        self.pieces = [Bitset::EMPTY; 6];
+        self.piece_hash = 0;
+        self.outcome = GameOutcome::default();
+        self.history.clear();
+        self.seat_players();
+
+        let rules = Arc::clone(&self.rules);
+        rules.setup(self);
+        self.position_hashes = vec![self.hash()];
+        self.reset_progress();
+        Ok(())
+    }
+
+    /// Splits the players into teams and seats them for a new game.
+    fn seat_players(&mut self) {
+        // a seat with several sides wins once all of them have finished
+        self.teams = if self.has_multi_side_seats() {
+            self.seats.iter().map(|seat| seat.sides.clone()).collect()
+        } else if self.team_play {
+            gen_teams(&self.players)
+        } else {
+            self.players
+                .iter()
+                .map(|&player| btreeset![player])
+                .collect()
+        };
+
+        if !self.has_multi_side_seats() {
+            self.seats = single_side_seats(&self.players);
+        }
     }
 
     pub fn put_player(&mut self, coord: HexCoord, player: Player) {
-        if !self.is_valid(coord) {
-            return;
+        if let Some(index) = geometry().index(coord) {
+            self.set_spot(index, Spot::Player(player));
         }
-        self.board.insert(coord, Spot::Player(player));
     }
 
     pub fn remove_player(&mut self, coord: HexCoord) {
-        if !self.is_valid(coord) {
-            return;
+        if let Some(index) = geometry().index(coord) {
+            self.set_spot(index, Spot::Empty);
         }
-        self.board.insert(coord, Spot::Empty);
     }
 
     pub fn get(&self, coord: &HexCoord) -> Option<&Spot> {
-        self.board.get(coord)
+        geometry().index(*coord).map(|index| &self.spots[index])
     }
 
-    fn is_valid(&self, coord: HexCoord) -> bool {
-        self.board.contains_key(&coord)
+    /// Every hole on the board together with what's in it.
+    pub fn iter(&self) -> impl Iterator<Item = (HexCoord, Spot)> + '_ {
+        geometry()
+            .coords()
+            .iter()
+            .copied()
+            .zip(self.spots.iter().copied())
+    }
+
+    pub fn occupied(&self) -> Bitset {
+        self.occupied
+    }
+
+    /// The holes taken by `player`'s pieces.
+    pub fn piece_set(&self, player: Player) -> Bitset {
+        self.pieces[player.index()]
+    }
+
+    fn set_spot(&mut self, index: usize, spot: Spot) {
+        let keys = zobrist::keys();
+        if let Spot::Player(player) = self.spots[index] {
+            self.pieces[player.index()].remove(index);
+            self.occupied.remove(index);
+            self.piece_hash ^= keys.piece(index, player);
+        }
+        if let Spot::Player(player) = spot {
+            self.pieces[player.index()].insert(index);
+            self.occupied.insert(index);
+            self.piece_hash ^= keys.piece(index, player);
+        }
+        self.spots[index] = spot;
     }
 
     fn swap(&mut self, coord1: HexCoord, coord2: HexCoord) {
-        if !self.is_valid(coord1) || !self.is_valid(coord2) {
-            return;
+        let geometry = geometry();
+        if let (Some(index1), Some(index2)) = (geometry.index(coord1), geometry.index(coord2)) {
+            let spot1 = self.spots[index1];
+            let spot2 = self.spots[index2];
+            self.set_spot(index1, spot2);
+            self.set_spot(index2, spot1);
         }
-        let spot1 = self.board.get(&coord1).unwrap().clone();
-        let spot2 = self.board.get(&coord2).unwrap().clone();
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.outcome.draw.is_some()
+            || (!self.teams.is_empty() && self.outcome.team_ranking.len() == self.teams.len())
+    }
+
+    pub fn team_of(&self, player: Player) -> BTreeSet<Player> {
+        self.teams
+            .iter()
+            .find(|team| team.contains(&player))
+            .cloned()
+            .unwrap_or_else(|| btreeset![player])
+    }
+
+    /// Whether every member of `player`'s team has finished, so that none of
+    /// them needs a turn any more.
+    pub fn is_out(&self, player: Player) -> bool {
+        self.team_of(player)
+            .iter()
+            .all(|&member| self.outcome.has_finished(member))
+    }
+
+    /// The players whose pieces may be moved this turn. When turns go round
+    /// per seat, that is every side of the seat. Once all of their own
+    /// pieces are home, players help finish their partners' pieces.
+    pub fn movable_players(&self) -> Vec<Player> {
+        let own_sides = match (self.turn_order, self.seat_of(self.turn)) {
+            (TurnOrder::PerSeat, Some(seat)) => seat.sides.clone(),
+            _ => btreeset![self.turn],
+        };
+        let unfinished = |sides: BTreeSet<Player>| -> Vec<Player> {
+            sides
+                .into_iter()
+                .filter(|&side| !self.outcome.has_finished(side))
+                .collect()
+        };
 
-        self.board.insert(coord1, spot2);
-        self.board.insert(coord2, spot1);
+        let own_unfinished = unfinished(own_sides);
+        if own_unfinished.is_empty() {
+            unfinished(self.team_of(self.turn))
+        } else {
+            own_unfinished
+        }
     }
 
     pub fn make_move(&mut self, start_coord: HexCoord, end_coord: HexCoord) {
-        if self.validate_move(start_coord, end_coord) {
-            self.swap(start_coord, end_coord);
+        if self.is_over() || !self.validate_move(start_coord, end_coord) {
+            return;
+        }
+        self.play_move(start_coord, end_coord);
+    }
+
+    /// Plays a move already known to be legal.
+    fn play_move(&mut self, start_coord: HexCoord, end_coord: HexCoord) {
+        self.swap(start_coord, end_coord);
+        self.history.push(Move {
+            player: self.turn,
+            start: start_coord,
+            end: end_coord,
+        });
+        self.update_progress(self.turn);
+        self.update_outcome();
+        if !self.is_over() {
             self.start_next_turn();
         }
+        self.position_hashes.push(self.hash());
+        self.check_draws();
     }
 
-    fn validate_move(&self, start_coord: HexCoord, end_coord: HexCoord) -> bool {
-        let start_spot = self.get(&start_coord);
-        let end_spot = self.get(&end_coord);
-        
-        if !matches!(start_spot, Some(spot) if spot == &self.turn)
-            || !matches!(end_spot, Some(Spot::Empty))
-        {
-            return false;
+    pub fn validate_move(&self, start_coord: HexCoord, end_coord: HexCoord) -> bool {
+        self.rules.is_legal_move(self, start_coord, end_coord)
+    }
+
+    /// Whether `end_coord` can be reached from `start_coord` by a single step
+    /// or a chain of jumps, ignoring whose piece is at `start_coord`.
+    pub fn is_reachable(&self, start_coord: HexCoord, end_coord: HexCoord) -> bool {
+        let geometry = geometry();
+        match (geometry.index(start_coord), geometry.index(end_coord)) {
+            (Some(start), Some(end)) => bitboard::destinations(start, self.occupied).contains(end),
+            _ => false,
         }
+    }
 
-        if start_coord.neighbors().contains(&end_coord) {
-            true
-        } else {
-            let mut jump_centers = vec![start_coord];
-            let mut traversed_jump_centers = Vec::new();
-            while !jump_centers.is_empty() {
-                let mut new_jump_centers = Vec::new();
-                for &jump_center in &jump_centers {
-                    for (neighbor, jump_neighbor) in jump_center
-                        .neighbors()
-                        .into_iter()
-                        .zip(jump_center.jump_neighbors())
-                    {
-                        if !traversed_jump_centers.contains(&jump_neighbor) {
-                            if let (Some(Spot::Player(_)), Some(Spot::Empty)) =
-                                (self.get(&neighbor), self.get(&jump_neighbor))
-                            {
-                                if jump_neighbor == end_coord {
-                                    return true;
-                                }
-                                new_jump_centers.push(jump_neighbor);
-                            }
-                        }
-                    }
-                }
-                traversed_jump_centers.append(&mut jump_centers);
-                jump_centers = new_jump_centers;
+    /// Every hole reachable from `start_coord` by a single step or a chain
+    /// of jumps.
+    pub fn destinations(&self, start_coord: HexCoord) -> Vec<HexCoord> {
+        let geometry = geometry();
+        match geometry.index(start_coord) {
+            Some(start) => bitboard::destinations(start, self.occupied)
+                .iter()
+                .map(|index| geometry.coord(index))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// The holes a piece passes through moving from `start_coord` to
+    /// `end_coord`, both included. Empty if it can't get there.
+    pub fn move_path(&self, start_coord: HexCoord, end_coord: HexCoord) -> Vec<HexCoord> {
+        let geometry = geometry();
+        match (geometry.index(start_coord), geometry.index(end_coord)) {
+            (Some(start), Some(end)) => bitboard::path(start, end, self.occupied)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|index| geometry.coord(index))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// How many jumps the move from `start_coord` to `end_coord` chains
+    /// together, 0 for a single step or a move that isn't possible.
+    pub fn jump_count(&self, start_coord: HexCoord, end_coord: HexCoord) -> usize {
+        match start_coord.distance(end_coord) {
+            1 => 0,
+            _ => self.move_path(start_coord, end_coord).len().max(1) - 1,
+        }
+    }
+
+    /// The coordinates of every piece belonging to `player`.
+    pub fn pieces(&self, player: Player) -> Vec<HexCoord> {
+        let geometry = geometry();
+        self.piece_set(player)
+            .iter()
+            .map(|index| geometry.coord(index))
+            .collect()
+    }
+
+    /// Every step or jump chain any movable piece could make, whether or not
+    /// the rules allow it.
+    pub fn reachable_moves(&self) -> Vec<(HexCoord, HexCoord)> {
+        let geometry = geometry();
+        let movable = self
+            .movable_players()
+            .into_iter()
+            .fold(Bitset::EMPTY, |set, player| set | self.piece_set(player));
+
+        let mut moves = Vec::new();
+        for start in movable.iter() {
+            for end in bitboard::destinations(start, self.occupied).iter() {
+                moves.push((geometry.coord(start), geometry.coord(end)));
             }
-            false
         }
+        moves
     }
 
-    pub fn start_next_turn(&mut self) {
-        self.turn = self
+    /// Every move the player whose turn it is may make.
+    pub fn legal_moves(&self) -> Vec<(HexCoord, HexCoord)> {
+        self.rules.legal_moves(self)
+    }
+
+    pub fn has_legal_move(&self) -> bool {
+        !self.legal_moves().is_empty()
+    }
+
+    /// How many steps `player`'s pieces still have to make to fill their
+    /// goal triangle, zero once they have. Counted as the steps to the tip of
+    /// the triangle, less the steps a finished triangle would need.
+    pub fn distance_to_goal(&self, player: Player) -> i32 {
+        let center = HexCoord::default();
+        let goal = self.goal(player);
+        let tip = match goal
+            .iter()
+            .copied()
+            .max_by_key(|coord| coord.distance(center))
+        {
+            Some(tip) => tip,
+            None => return 0,
+        };
+
+        let pieces = self.pieces(player);
+        let mut packed: Vec<i32> = goal.iter().map(|coord| coord.distance(tip)).collect();
+        packed.sort_unstable();
+        let packed_distance: i32 = packed.into_iter().take(pieces.len()).sum();
+
+        let distance: i32 = pieces.into_iter().map(|piece| piece.distance(tip)).sum();
+        distance - packed_distance
+    }
+
+    /// The number of moves `player` has made since the game was set up.
+    pub fn turns_taken(&self, player: Player) -> usize {
+        self.history
+            .iter()
+            .filter(|past_move| past_move.player == player)
+            .count()
+    }
+
+    fn update_outcome(&mut self) {
+        let newly_finished: Vec<Player> = self
             .players
             .iter()
-            .cycle()
-            .skip_while(|&&player| player != self.turn)
-            .skip(1)
-            .next()
             .copied()
-            .unwrap();
+            .filter(|&player| !self.outcome.has_finished(player))
+            .filter(|&player| self.rules.has_finished(self, player))
+            .collect();
+        self.outcome.ranking.extend(newly_finished);
+
+        let newly_finished_teams: Vec<BTreeSet<Player>> = self
+            .teams
+            .iter()
+            .filter(|team| !self.outcome.team_ranking.contains(team))
+            .filter(|team| team.iter().all(|&member| self.outcome.has_finished(member)))
+            .cloned()
+            .collect();
+        self.outcome.team_ranking.extend(newly_finished_teams);
+
+        // the last team left has nobody to race against
+        let remaining: Vec<BTreeSet<Player>> = self
+            .teams
+            .iter()
+            .filter(|team| !self.outcome.team_ranking.contains(team))
+            .cloned()
+            .collect();
+        if let [last_team] = remaining.as_slice() {
+            if self.teams.len() > 1 {
+                for &member in last_team {
+                    if !self.outcome.has_finished(member) {
+                        self.outcome.ranking.push(member);
+                    }
+                }
+                self.outcome.team_ranking.push(last_team.clone());
+            }
+        }
+    }
+
+    /// Passes the turn on, skipping players that have no legal move. If
+    /// nobody can move any more the game ends in a stalemate.
+    pub fn start_next_turn(&mut self) {
+        for _ in 0..self.players.len() {
+            self.turn = self.rules.next_turn(self);
+            if self.has_legal_move() {
+                return;
+            }
+        }
+        self.outcome.draw = Some(Draw::Stalemate);
     }
 }
 
-fn gen_empty_board() -> HashMap<HexCoord, Spot> {
+/// Groups `players` into teams of partners, where your partners sit next to
+/// the side opposite you. Only 4 and 6 players split into more than one team,
+/// anyone else plays alone.
+pub fn gen_teams(players: &BTreeSet<Player>) -> Vec<BTreeSet<Player>> {
+    let mut teams: Vec<BTreeSet<Player>> = Vec::new();
+    for &player in players {
+        let partners = [player.opposite().forward(), player.opposite().backward()];
+        match teams
+            .iter_mut()
+            .find(|team| partners.iter().any(|partner| team.contains(partner)))
+        {
+            Some(team) => {
+                team.insert(player);
+            }
+            None => teams.push(btreeset![player]),
+        }
+    }
+
+    if teams.len() < 2 || teams.iter().any(|team| team.len() < 2) {
+        return players.iter().map(|&player| btreeset![player]).collect();
+    }
+    teams
+}
+
+/// Every hole of the star, in no particular order.
+fn gen_holes() -> Vec<HexCoord> {
     let big_triangle = HexCoord::new(4, -8).triangle_tip_up(13).into_iter();
 
     let triangle_1 = HexCoord::new(-4, -1).triangle_tip_down(4).into_iter();
@@ -346,23 +780,5 @@ fn gen_empty_board() -> HashMap<HexCoord, Spot> {
         .chain(triangle_1)
         .chain(triangle_2)
         .chain(triangle_3)
-        .map(|coord| (coord, Spot::default()))
         .collect()
 }
-
-fn gen_players(players_count: usize) -> BTreeSet<Player> {
-    use maplit::btreeset;
-    use SideOfStar::*;
-    let players_count = match players_count {
-        2 | 3 | 4 | 6 => players_count,
-        _ => 2,
-    };
-
-    match players_count {
-        2 => btreeset![A, D],
-        3 => btreeset![A, C, E],
-        4 => btreeset![A, B, D, E],
-        6 => btreeset![A, B, C, D, E, F],
-        _ => unreachable!(),
-    }
-}
@@ -0,0 +1,238 @@
+//! A compact representation of the star: every hole gets an index below
+//! `HOLES`, so a set of holes fits in a single `u128`, and the neighbors and
+//! jumps of every hole are worked out once up front.
+
+use std::collections::HashMap;
+use std::ops;
+use std::sync::OnceLock;
+
+use super::HexCoord;
+
+pub const HOLES: usize = 121;
+
+/// both axes of the star run from -8 to 8
+const AXIS_OFFSET: i32 = 8;
+const AXIS_SPAN: usize = 17;
+
+/// A set of holes, one bit per hole index.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Bitset(pub u128);
+
+impl Bitset {
+    pub const EMPTY: Self = Self(0);
+
+    pub fn single(index: usize) -> Self {
+        Self(1 << index)
+    }
+
+    pub fn contains(self, index: usize) -> bool {
+        self.0 & (1 << index) != 0
+    }
+
+    pub fn insert(&mut self, index: usize) {
+        self.0 |= 1 << index;
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        self.0 &= !(1 << index);
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// The indices in the set, lowest first.
+    pub fn iter(self) -> impl Iterator<Item = usize> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let index = bits.trailing_zeros() as usize;
+            bits &= bits - 1;
+            Some(index)
+        })
+    }
+}
+
+impl ops::BitOr for Bitset {
+    type Output = Self;
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl ops::BitAnd for Bitset {
+    type Output = Self;
+    fn bitand(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+}
+
+impl ops::Not for Bitset {
+    type Output = Self;
+    fn not(self) -> Self {
+        Self(!self.0 & ALL_HOLES)
+    }
+}
+
+impl ops::BitOrAssign for Bitset {
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+const ALL_HOLES: u128 = (1 << HOLES) - 1;
+
+/// The precomputed layout of the star.
+#[derive(Debug)]
+pub struct Geometry {
+    coords: Vec<HexCoord>,
+    indices: [[Option<u8>; AXIS_SPAN]; AXIS_SPAN],
+    /// the holes next to each hole
+    pub neighbors: Vec<Bitset>,
+    /// for each hole, the (jumped over, landing) holes of every jump
+    pub jumps: Vec<Vec<(usize, usize)>>,
+}
+
+impl Geometry {
+    fn new(holes: impl IntoIterator<Item = HexCoord>) -> Self {
+        let mut coords: Vec<HexCoord> = holes.into_iter().collect();
+        coords.sort_by_key(|coord| (coord.slant, coord.horz));
+        coords.dedup();
+        assert_eq!(coords.len(), HOLES);
+
+        let mut indices = [[None; AXIS_SPAN]; AXIS_SPAN];
+        for (index, coord) in coords.iter().enumerate() {
+            let (horz, slant) = axis_position(*coord).unwrap();
+            indices[horz][slant] = Some(index as u8);
+        }
+        let lookup: HashMap<HexCoord, usize> = coords
+            .iter()
+            .enumerate()
+            .map(|(index, &coord)| (coord, index))
+            .collect();
+
+        let neighbors = coords
+            .iter()
+            .map(|coord| {
+                coord
+                    .neighbors()
+                    .iter()
+                    .filter_map(|neighbor| lookup.get(neighbor))
+                    .fold(Bitset::EMPTY, |set, &index| set | Bitset::single(index))
+            })
+            .collect();
+        let jumps = coords
+            .iter()
+            .map(|coord| {
+                coord
+                    .neighbors()
+                    .into_iter()
+                    .zip(coord.jump_neighbors())
+                    .filter_map(|(over, landing)| {
+                        Some((*lookup.get(&over)?, *lookup.get(&landing)?))
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            coords,
+            indices,
+            neighbors,
+            jumps,
+        }
+    }
+
+    pub fn index(&self, coord: HexCoord) -> Option<usize> {
+        let (horz, slant) = axis_position(coord)?;
+        self.indices[horz][slant].map(usize::from)
+    }
+
+    pub fn coord(&self, index: usize) -> HexCoord {
+        self.coords[index]
+    }
+
+    pub fn coords(&self) -> &[HexCoord] {
+        &self.coords
+    }
+}
+
+fn axis_position(coord: HexCoord) -> Option<(usize, usize)> {
+    let horz = coord.horz + AXIS_OFFSET;
+    let slant = coord.slant + AXIS_OFFSET;
+    let span = 0..AXIS_SPAN as i32;
+    if span.contains(&horz) && span.contains(&slant) {
+        Some((horz as usize, slant as usize))
+    } else {
+        None
+    }
+}
+
+pub fn geometry() -> &'static Geometry {
+    static GEOMETRY: OnceLock<Geometry> = OnceLock::new();
+    GEOMETRY.get_or_init(|| Geometry::new(super::gen_holes()))
+}
+
+/// Every hole reachable from `start` by a single step or a chain of jumps,
+/// given the `occupied` holes.
+pub fn destinations(start: usize, occupied: Bitset) -> Bitset {
+    let geometry = geometry();
+    let steps = geometry.neighbors[start] & !occupied;
+
+    let mut landed = Bitset::EMPTY;
+    let mut jump_centers = vec![start];
+    while let Some(jump_center) = jump_centers.pop() {
+        for &(over, landing) in &geometry.jumps[jump_center] {
+            if occupied.contains(over) && !occupied.contains(landing) && !landed.contains(landing) {
+                landed.insert(landing);
+                jump_centers.push(landing);
+            }
+        }
+    }
+    steps | landed
+}
+
+/// The holes a piece passes through going from `start` to `end`, both
+/// included, preferring a single step and otherwise the fewest jumps.
+pub fn path(start: usize, end: usize, occupied: Bitset) -> Option<Vec<usize>> {
+    let geometry = geometry();
+    if geometry.neighbors[start].contains(end) && !occupied.contains(end) {
+        return Some(vec![start, end]);
+    }
+
+    let mut came_from = vec![None; HOLES];
+    let mut visited = Bitset::single(start);
+    let mut frontier = vec![start];
+    while !frontier.is_empty() && !visited.contains(end) {
+        let mut next_frontier = Vec::new();
+        for &jump_center in &frontier {
+            for &(over, landing) in &geometry.jumps[jump_center] {
+                if occupied.contains(over)
+                    && !occupied.contains(landing)
+                    && !visited.contains(landing)
+                {
+                    visited.insert(landing);
+                    came_from[landing] = Some(jump_center);
+                    next_frontier.push(landing);
+                }
+            }
+        }
+        frontier = next_frontier;
+    }
+
+    if !visited.contains(end) || start == end {
+        return None;
+    }
+    let mut path = vec![end];
+    while let Some(previous) = came_from[*path.last().unwrap()] {
+        path.push(previous);
+    }
+    path.reverse();
+    Some(path)
+}
@@ -0,0 +1,101 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Board, Player};
+
+/// Why a game ended without everybody finishing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Draw {
+    /// nobody left could move
+    Stalemate,
+    /// the same position came up too often
+    Repetition,
+    /// too many moves went by without anyone getting closer to their goal
+    NoProgress,
+    /// the players agreed to a draw
+    Agreed,
+}
+
+impl Draw {
+    pub fn describe(self) -> &'static str {
+        match self {
+            Self::Stalemate => "stalemate",
+            Self::Repetition => "repetition",
+            Self::NoProgress => "no progress",
+            Self::Agreed => "agreement",
+        }
+    }
+}
+
+/// When a game is declared a draw. By default only when nobody can move.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DrawRules {
+    /// draw once the same position, with the same player to move, has come
+    /// up this many times
+    pub repetitions: Option<usize>,
+    /// draw after this many moves in a row where no player got their pieces
+    /// closer to their goal than ever before
+    pub no_progress_moves: Option<usize>,
+}
+
+/// Tracks how close every player has come to their goal.
+#[derive(Debug, Clone, Default)]
+pub(super) struct Progress {
+    best_distances: BTreeMap<Player, i32>,
+    moves_without_progress: usize,
+}
+
+impl Board {
+    pub fn agree_draw(&mut self) {
+        if !self.is_over() {
+            self.outcome.draw = Some(Draw::Agreed);
+        }
+    }
+
+    /// The moves in a row in which nobody got closer to their goal.
+    pub fn moves_without_progress(&self) -> usize {
+        self.progress.moves_without_progress
+    }
+
+    pub(super) fn reset_progress(&mut self) {
+        self.progress = Progress {
+            best_distances: self
+                .players
+                .iter()
+                .map(|&player| (player, self.distance_to_goal(player)))
+                .collect(),
+            moves_without_progress: 0,
+        };
+    }
+
+    pub(super) fn update_progress(&mut self, player: Player) {
+        let distance = self.distance_to_goal(player);
+        let best = self
+            .progress
+            .best_distances
+            .entry(player)
+            .or_insert(distance);
+        if distance < *best {
+            *best = distance;
+            self.progress.moves_without_progress = 0;
+        } else {
+            self.progress.moves_without_progress += 1;
+        }
+    }
+
+    /// Ends the game in a draw if one of the `draw_rules` applies.
+    pub(super) fn check_draws(&mut self) {
+        if self.is_over() {
+            return;
+        }
+
+        let rules = self.draw_rules;
+        if matches!(rules.repetitions, Some(limit) if self.repetitions() >= limit) {
+            self.outcome.draw = Some(Draw::Repetition);
+        } else if matches!(rules.no_progress_moves, Some(limit) if self.moves_without_progress() >= limit)
+        {
+            self.outcome.draw = Some(Draw::NoProgress);
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use super::Board;
+
+impl Board {
+    /// Counts the positions reachable in exactly `depth` moves. Positions
+    /// where the game is over count as leaves even before `depth`.
+    ///
+    /// Known counts are checked in `tests/perft.rs`, which makes this the
+    /// regression test for move generation.
+    pub fn perft(&self, depth: usize) -> u64 {
+        if depth == 0 || self.is_over() {
+            return 1;
+        }
+
+        let moves = self.legal_moves();
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+
+        moves
+            .into_iter()
+            .map(|(start, end)| {
+                let mut board = self.clone();
+                board.play_move(start, end);
+                board.perft(depth - 1)
+            })
+            .sum()
+    }
+}
@@ -0,0 +1,344 @@
+//! Positions other than the start: checking them, starting games from
+//! them, and writing them down as one line of text.
+//!
+//! A position string has three fields separated by spaces, e.g.
+//! `A/AA/AAA/AAAA/13/12/11/10/9/10/11/12/13/DDDD/DDD/DD/D AD A` for the
+//! start of a two player game:
+//!
+//! 1. the 17 rows of the star from top to bottom, separated by `/`, each
+//!    listing its holes from left to right: a side letter for a piece and
+//!    a number for a run of empty holes
+//! 2. the sides playing
+//! 3. the side to move
+
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fmt;
+
+use super::bitboard::geometry;
+use super::{Board, GameOutcome, Player, SetupError, SideOfStar, Spot, StandardRules};
+
+/// the pieces every side plays with
+pub const PIECES_PER_SIDE: usize = 10;
+
+/// Why the pieces on a `Board` don't make a position a game can start from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PositionError {
+    Setup(SetupError),
+    /// the side has this many pieces instead of `PIECES_PER_SIDE`
+    PieceCount(Player, usize),
+    /// a side that isn't playing has pieces on the board
+    StrayPieces(Player),
+    /// the side to move isn't playing
+    TurnNotPlaying(Player),
+    /// the side has already filled its goal
+    AlreadyFinished(Player),
+}
+
+impl fmt::Display for PositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Setup(error) => error.fmt(f),
+            Self::PieceCount(player, count) => write!(
+                f,
+                "side {:?} has {} pieces instead of {}",
+                player, count, PIECES_PER_SIDE
+            ),
+            Self::StrayPieces(player) => {
+                write!(f, "side {:?} has pieces but isn't playing", player)
+            }
+            Self::TurnNotPlaying(player) => {
+                write!(f, "it's side {:?}'s turn but it isn't playing", player)
+            }
+            Self::AlreadyFinished(player) => {
+                write!(f, "side {:?} has already filled its goal", player)
+            }
+        }
+    }
+}
+
+impl Error for PositionError {}
+
+impl From<SetupError> for PositionError {
+    fn from(error: SetupError) -> Self {
+        Self::Setup(error)
+    }
+}
+
+impl Board {
+    /// Checks that a game can start from the pieces as they are.
+    pub fn validate_position(&self) -> Result<(), PositionError> {
+        self.validate_setup()?;
+
+        for side in Player::all() {
+            let count = self.piece_set(side).len();
+            if self.players.contains(&side) {
+                if count != PIECES_PER_SIDE {
+                    return Err(PositionError::PieceCount(side, count));
+                }
+                if self.rules.has_finished(self, side) {
+                    return Err(PositionError::AlreadyFinished(side));
+                }
+            } else if count > 0 {
+                return Err(PositionError::StrayPieces(side));
+            }
+        }
+
+        if !self.players.contains(&self.turn) {
+            return Err(PositionError::TurnNotPlaying(self.turn));
+        }
+        Ok(())
+    }
+
+    /// Starts a game from the pieces as they are, rather than from the home
+    /// triangles like `setup_players`.
+    pub fn start_from_position(&mut self) -> Result<(), PositionError> {
+        self.validate_position()?;
+        self.restart_from_pieces();
+        Ok(())
+    }
+
+    /// Forgets how the pieces got where they are.
+    fn restart_from_pieces(&mut self) {
+        self.outcome = GameOutcome::default();
+        self.history.clear();
+        self.seat_players();
+        self.position_hashes = vec![self.hash()];
+        self.reset_progress();
+    }
+
+    /// Removes every piece from the board.
+    pub fn clear_pieces(&mut self) {
+        for (coord, spot) in self.iter().collect::<Vec<_>>() {
+            if spot != Spot::Empty {
+                self.remove_player(coord);
+            }
+        }
+    }
+}
+
+/// Why a position string couldn't be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParsePositionError {
+    /// there should be 3 fields separated by spaces
+    FieldCount(usize),
+    /// there should be 17 rows separated by `/`
+    RowCount(usize),
+    /// the row, counting from 1, doesn't have as many holes as it should
+    RowLength {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// neither a side letter nor a digit in the row, counting from 1
+    UnknownSymbol {
+        row: usize,
+        symbol: char,
+    },
+    /// not the letter of a side, A to F
+    UnknownSide(char),
+    DuplicateSide(Player),
+    /// the turn field should be a single side letter
+    Turn(String),
+    Setup(SetupError),
+}
+
+impl fmt::Display for ParsePositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FieldCount(count) => write!(
+                f,
+                "expected 3 fields (rows, sides, turn) separated by spaces, found {}",
+                count
+            ),
+            Self::RowCount(count) => {
+                write!(
+                    f,
+                    "expected {} rows separated by '/', found {}",
+                    ROWS, count
+                )
+            }
+            Self::RowLength {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {} should have {} holes but has {}",
+                row, expected, found
+            ),
+            Self::UnknownSymbol { row, symbol } => write!(
+                f,
+                "row {} has {:?}, which is neither a side nor a number of empty holes",
+                row, symbol
+            ),
+            Self::UnknownSide(symbol) => write!(f, "{:?} isn't a side, A to F", symbol),
+            Self::DuplicateSide(side) => write!(f, "side {:?} is listed twice", side),
+            Self::Turn(turn) => write!(
+                f,
+                "the turn should be one of the sides playing, not {:?}",
+                turn
+            ),
+            Self::Setup(error) => error.fmt(f),
+        }
+    }
+}
+
+impl Error for ParsePositionError {}
+
+impl From<SetupError> for ParsePositionError {
+    fn from(error: SetupError) -> Self {
+        Self::Setup(error)
+    }
+}
+
+/// the rows of holes of the star
+const ROWS: usize = 17;
+
+/// The holes of each row of the star as `bitboard` indices, top to bottom
+/// and left to right.
+fn rows() -> Vec<Vec<usize>> {
+    let mut rows: Vec<Vec<usize>> = Vec::with_capacity(ROWS);
+    let mut row_slant = None;
+    // the indices are sorted by slant, then from left to right
+    for (index, coord) in geometry().coords().iter().enumerate() {
+        if row_slant != Some(coord.slant) {
+            row_slant = Some(coord.slant);
+            rows.push(Vec::new());
+        }
+        rows.last_mut().unwrap().push(index);
+    }
+    rows
+}
+
+fn side_from_letter(letter: char) -> Option<SideOfStar> {
+    use SideOfStar::*;
+    match letter {
+        'A' => Some(A),
+        'B' => Some(B),
+        'C' => Some(C),
+        'D' => Some(D),
+        'E' => Some(E),
+        'F' => Some(F),
+        _ => None,
+    }
+}
+
+impl Board {
+    /// The pieces, the sides playing and the side to move as one line of
+    /// text, see the module docs.
+    pub fn to_position_string(&self) -> String {
+        let rows: Vec<String> = rows()
+            .into_iter()
+            .map(|row| {
+                let mut text = String::new();
+                let mut empty_run = 0;
+                for index in row {
+                    match self.spots[index] {
+                        Spot::Empty => empty_run += 1,
+                        Spot::Player(player) => {
+                            if empty_run > 0 {
+                                text += &empty_run.to_string();
+                                empty_run = 0;
+                            }
+                            text += &format!("{:?}", player);
+                        }
+                    }
+                }
+                if empty_run > 0 {
+                    text += &empty_run.to_string();
+                }
+                text
+            })
+            .collect();
+        let players: String = self
+            .players
+            .iter()
+            .map(|player| format!("{:?}", player))
+            .collect();
+        format!("{} {} {:?}", rows.join("/"), players, self.turn)
+    }
+
+    /// A board with the position written by `to_position_string`, playing
+    /// by the `StandardRules` with every side aiming for the opposite
+    /// triangle. The pieces aren't checked, see `validate_position`.
+    pub fn from_position_string(position: &str) -> Result<Self, ParsePositionError> {
+        let fields: Vec<&str> = position.split_whitespace().collect();
+        let (rows_field, players_field, turn_field) = match fields.as_slice() {
+            &[rows, players, turn] => (rows, players, turn),
+            _ => return Err(ParsePositionError::FieldCount(fields.len())),
+        };
+
+        let mut players = BTreeSet::new();
+        for letter in players_field.chars() {
+            let side = side_from_letter(letter).ok_or(ParsePositionError::UnknownSide(letter))?;
+            if !players.insert(side) {
+                return Err(ParsePositionError::DuplicateSide(side));
+            }
+        }
+        let mut turn_letters = turn_field.chars();
+        let turn = match (
+            turn_letters.next().and_then(side_from_letter),
+            turn_letters.next(),
+        ) {
+            (Some(turn), None) if players.contains(&turn) => turn,
+            _ => return Err(ParsePositionError::Turn(turn_field.to_string())),
+        };
+
+        let row_texts: Vec<&str> = rows_field.split('/').collect();
+        if row_texts.len() != ROWS {
+            return Err(ParsePositionError::RowCount(row_texts.len()));
+        }
+        let mut spots = Vec::new();
+        for (row_number, (text, holes)) in row_texts.iter().zip(rows()).enumerate() {
+            let row = row_number + 1;
+            let mut row_spots = Vec::new();
+            let mut symbols = text.chars().peekable();
+            while let Some(symbol) = symbols.next() {
+                if let Some(digit) = symbol.to_digit(10) {
+                    let mut empty_run = digit as usize;
+                    loop {
+                        // checked before every digit, so a long run of
+                        // digits can't overflow
+                        if empty_run > holes.len() {
+                            return Err(ParsePositionError::RowLength {
+                                row,
+                                expected: holes.len(),
+                                found: row_spots.len() + empty_run,
+                            });
+                        }
+                        match symbols.peek().and_then(|next| next.to_digit(10)) {
+                            Some(digit) => {
+                                empty_run = empty_run * 10 + digit as usize;
+                                symbols.next();
+                            }
+                            None => break,
+                        }
+                    }
+                    row_spots.extend((0..empty_run).map(|_| Spot::Empty));
+                } else {
+                    let side = side_from_letter(symbol)
+                        .ok_or(ParsePositionError::UnknownSymbol { row, symbol })?;
+                    row_spots.push(Spot::Player(side));
+                }
+            }
+            if row_spots.len() != holes.len() {
+                return Err(ParsePositionError::RowLength {
+                    row,
+                    expected: holes.len(),
+                    found: row_spots.len(),
+                });
+            }
+            spots.extend(holes.into_iter().zip(row_spots));
+        }
+
+        let mut board = Self::with_players(players, StandardRules)?;
+        board.clear_pieces();
+        for (index, spot) in spots {
+            board.set_spot(index, spot);
+        }
+        board.turn = turn;
+        board.restart_from_pieces();
+        Ok(board)
+    }
+}
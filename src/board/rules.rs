@@ -0,0 +1,209 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use maplit::btreeset;
+use serde::{Deserialize, Serialize};
+
+use super::{Board, HexCoord, Player, SetupError, SideOfStar, Spot, TurnOrder};
+
+/// The rules of a variant of the game.
+///
+/// `Board` only knows about the geometry of the star; everything that
+/// decides how a game is played is asked from its `RuleSet`.
+pub trait RuleSet: fmt::Debug + Send + Sync {
+    /// The sides that take part in a game of `players_count` players.
+    fn players(&self, players_count: usize) -> Result<BTreeSet<Player>, SetupError> {
+        gen_players(players_count)
+    }
+
+    /// Places the pieces of `board.players` on an empty board and picks who
+    /// moves first.
+    fn setup(&self, board: &mut Board) {
+        for player in board.players.clone() {
+            for coord in player.home() {
+                board.put_player(coord, player);
+            }
+        }
+
+        if let Some(&first) = board.players.iter().next() {
+            board.turn = first;
+        }
+    }
+
+    fn is_legal_move(&self, board: &Board, start_coord: HexCoord, end_coord: HexCoord) -> bool;
+
+    /// Every legal move for `board.turn`.
+    fn legal_moves(&self, board: &Board) -> Vec<(HexCoord, HexCoord)> {
+        board
+            .reachable_moves()
+            .into_iter()
+            .filter(|&(start, end)| self.is_legal_move(board, start, end))
+            .collect()
+    }
+
+    /// The player who moves after `board.turn`.
+    fn next_turn(&self, board: &Board) -> Player {
+        if board.turn_order == TurnOrder::PerSeat {
+            return next_seat_turn(board);
+        }
+
+        board
+            .players
+            .iter()
+            .cycle()
+            .skip_while(|&&player| player != board.turn)
+            .skip(1)
+            .take(board.players.len())
+            .find(|&&player| !board.is_out(player))
+            .copied()
+            .unwrap_or(board.turn)
+    }
+
+    /// Whether `player` has reached its win condition.
+    fn has_finished(&self, board: &Board, player: Player) -> bool {
+        board
+            .goal(player)
+            .iter()
+            .all(|coord| matches!(board.get(coord), Some(spot) if spot == &player))
+    }
+}
+
+/// The classic Sternhalma rules: move one of your own pieces a single step
+/// or along a chain of jumps, and fill the opposite triangle to finish.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StandardRules;
+
+impl RuleSet for StandardRules {
+    fn is_legal_move(&self, board: &Board, start_coord: HexCoord, end_coord: HexCoord) -> bool {
+        matches!(board.get(&start_coord), Some(Spot::Player(player)) if board.movable_players().contains(player))
+            && matches!(board.get(&end_coord), Some(Spot::Empty))
+            && board.is_reachable(start_coord, end_coord)
+    }
+
+    fn legal_moves(&self, board: &Board) -> Vec<(HexCoord, HexCoord)> {
+        // every reachable move of a movable piece is legal
+        board.reachable_moves()
+    }
+}
+
+/// The first side of the next seat still in the game, preferring sides that
+/// have not finished yet.
+fn next_seat_turn(board: &Board) -> Player {
+    let seats = &board.seats;
+    let current = seats
+        .iter()
+        .position(|seat| seat.owns(board.turn))
+        .unwrap_or(0);
+
+    (1..=seats.len())
+        .map(|offset| &seats[(current + offset) % seats.len()])
+        .filter(|seat| seat.sides.iter().any(|&side| !board.is_out(side)))
+        .find_map(|seat| {
+            seat.sides
+                .iter()
+                .find(|&&side| !board.outcome.has_finished(side))
+                .or_else(|| seat.sides.iter().next())
+                .copied()
+        })
+        .unwrap_or(board.turn)
+}
+
+fn gen_players(players_count: usize) -> Result<BTreeSet<Player>, SetupError> {
+    use SideOfStar::*;
+    match players_count {
+        1 => Ok(btreeset![A]),
+        2 => Ok(btreeset![A, D]),
+        3 => Ok(btreeset![A, C, E]),
+        4 => Ok(btreeset![A, B, D, E]),
+        5 => Ok(btreeset![A, B, C, D, E]),
+        6 => Ok(btreeset![A, B, C, D, E, F]),
+        0 => Err(SetupError::NoPlayers),
+        _ => Err(SetupError::UnsupportedPlayerCount(players_count)),
+    }
+}
+
+/// Rules against spoiling, i.e. leaving pieces in your own home triangle to
+/// stop the opposite player from finishing. Wraps any other `RuleSet`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AntiSpoiling<R = StandardRules> {
+    pub inner: R,
+    /// A player has finished once every goal hole is occupied and at least
+    /// one of them by their own piece, even if the side whose home the goal
+    /// is blocks the rest.
+    pub blocked_goal_counts_as_filled: bool,
+    /// After this many of their own turns a player must move pieces out of
+    /// their home triangle whenever they can.
+    pub evacuate_home_after: Option<usize>,
+}
+
+impl<R: RuleSet> AntiSpoiling<R> {
+    fn must_evacuate(&self, board: &Board, player: Player) -> bool {
+        match self.evacuate_home_after {
+            Some(turns) => board.turns_taken(player) >= turns,
+            None => false,
+        }
+    }
+}
+
+impl<R: RuleSet> RuleSet for AntiSpoiling<R> {
+    fn players(&self, players_count: usize) -> Result<BTreeSet<Player>, SetupError> {
+        self.inner.players(players_count)
+    }
+
+    fn setup(&self, board: &mut Board) {
+        self.inner.setup(board)
+    }
+
+    fn is_legal_move(&self, board: &Board, start_coord: HexCoord, end_coord: HexCoord) -> bool {
+        if !self.inner.is_legal_move(board, start_coord, end_coord) {
+            return false;
+        }
+        if !self.must_evacuate(board, board.turn) {
+            return true;
+        }
+
+        let home = board.turn.home();
+        let leaves_home =
+            |start: &HexCoord, end: &HexCoord| home.contains(start) && !home.contains(end);
+        if leaves_home(&start_coord, &end_coord) {
+            return true;
+        }
+
+        // only restrict the player if one of the stragglers can actually
+        // get out, otherwise they would be stuck
+        let can_evacuate = home
+            .iter()
+            .filter(|coord| matches!(board.get(coord), Some(spot) if spot == &board.turn))
+            .any(|&start| {
+                board.destinations(start).iter().any(|end| {
+                    leaves_home(&start, end) && self.inner.is_legal_move(board, start, *end)
+                })
+            });
+        !can_evacuate
+    }
+
+    fn next_turn(&self, board: &Board) -> Player {
+        self.inner.next_turn(board)
+    }
+
+    fn has_finished(&self, board: &Board, player: Player) -> bool {
+        if self.inner.has_finished(board, player) {
+            return true;
+        }
+        if !self.blocked_goal_counts_as_filled {
+            return false;
+        }
+
+        // only the spoiler, the side whose home the goal is, can block it;
+        // anyone else's pieces are just passing through
+        let spoiler = board.goal_side(player);
+        let goal = board.goal(player);
+        let all_filled = goal.iter().all(|coord| {
+            matches!(board.get(coord), Some(&Spot::Player(side)) if side == player || side == spoiler)
+        });
+        let own_piece_in_goal = goal
+            .iter()
+            .any(|coord| matches!(board.get(coord), Some(spot) if spot == &player));
+        all_filled && own_piece_in_goal
+    }
+}
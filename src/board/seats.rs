@@ -0,0 +1,72 @@
+use std::collections::BTreeSet;
+
+use maplit::btreeset;
+use serde::{Deserialize, Serialize};
+
+use super::{Player, SideOfStar};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SeatKind {
+    #[default]
+    Human,
+    Computer,
+}
+
+/// Someone sitting at the board, controlling the pieces of one or more sides.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Seat {
+    pub kind: SeatKind,
+    pub sides: BTreeSet<SideOfStar>,
+}
+
+impl Seat {
+    pub fn new(sides: BTreeSet<SideOfStar>) -> Self {
+        Self {
+            kind: SeatKind::default(),
+            sides,
+        }
+    }
+
+    pub fn owns(&self, side: SideOfStar) -> bool {
+        self.sides.contains(&side)
+    }
+}
+
+/// How turns rotate when a seat owns more than one side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TurnOrder {
+    /// every side moves in turn, so a seat with two sides moves twice a round
+    #[default]
+    PerSide,
+    /// every seat moves once a round, with a piece of any side it owns
+    PerSeat,
+}
+
+/// One seat for each player in `players`.
+pub fn single_side_seats(players: &BTreeSet<Player>) -> Vec<Seat> {
+    players
+        .iter()
+        .map(|&player| Seat::new(btreeset![player]))
+        .collect()
+}
+
+/// The seats for `seats_count` people playing `sides_per_seat` sides each.
+/// Only the layouts that fit on the star are supported.
+pub fn gen_seats(seats_count: usize, sides_per_seat: usize) -> Option<Vec<Seat>> {
+    use SideOfStar::*;
+    let sides = match (seats_count, sides_per_seat) {
+        (2, 1) => vec![btreeset![A], btreeset![D]],
+        (3, 1) => vec![btreeset![A], btreeset![C], btreeset![E]],
+        (4, 1) => vec![btreeset![A], btreeset![B], btreeset![D], btreeset![E]],
+        (6, 1) => SideOfStar::all()
+            .into_iter()
+            .map(|side| btreeset![side])
+            .collect(),
+        (2, 2) => vec![btreeset![A, B], btreeset![D, E]],
+        (2, 3) => vec![btreeset![A, C, E], btreeset![B, D, F]],
+        (3, 2) => vec![btreeset![A, B], btreeset![C, D], btreeset![E, F]],
+        _ => return None,
+    };
+
+    Some(sides.into_iter().map(Seat::new).collect())
+}
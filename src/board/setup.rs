@@ -0,0 +1,76 @@
+use std::error::Error;
+use std::fmt;
+
+use super::{gen_teams, Board, Player, SideOfStar};
+
+/// Why a `Board` can't be set up with its current configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetupError {
+    NoPlayers,
+    UnsupportedPlayerCount(usize),
+    /// the side would have to reach the triangle it starts in
+    GoalIsHome(Player),
+    /// both sides race for the same goal triangle
+    SharedGoal(Player, Player, SideOfStar),
+    /// team play was asked for but the players can't be split into teams
+    NoTeams,
+}
+
+impl fmt::Display for SetupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoPlayers => write!(f, "select at least one side"),
+            Self::UnsupportedPlayerCount(count) => {
+                write!(f, "a game needs 1 to 6 players, not {}", count)
+            }
+            Self::GoalIsHome(player) => {
+                write!(
+                    f,
+                    "side {:?} can't have its own triangle as its goal",
+                    player
+                )
+            }
+            Self::SharedGoal(first, second, goal) => write!(
+                f,
+                "sides {:?} and {:?} both have triangle {:?} as their goal",
+                first, second, goal
+            ),
+            Self::NoTeams => write!(f, "team play needs 4 or 6 sides"),
+        }
+    }
+}
+
+impl Error for SetupError {}
+
+impl Board {
+    /// Checks that the players, goals and teams make up a playable game.
+    pub fn validate_setup(&self) -> Result<(), SetupError> {
+        if self.players.is_empty() {
+            return Err(SetupError::NoPlayers);
+        }
+
+        for &player in &self.players {
+            let goal = self.goal_side(player);
+            if goal == player {
+                return Err(SetupError::GoalIsHome(player));
+            }
+            if let Some(&other) = self
+                .players
+                .iter()
+                .take_while(|&&other| other != player)
+                .find(|&&other| self.goal_side(other) == goal)
+            {
+                return Err(SetupError::SharedGoal(other, player, goal));
+            }
+        }
+
+        if self.team_play
+            && !self.has_multi_side_seats()
+            && gen_teams(&self.players).iter().all(|team| team.len() < 2)
+        {
+            return Err(SetupError::NoTeams);
+        }
+
+        Ok(())
+    }
+}
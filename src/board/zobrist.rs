@@ -0,0 +1,74 @@
+//! Zobrist hashing: every (hole, side) pair and every side to move gets a
+//! random key, and a position hashes to the xor of the keys that apply.
+//! Moving a piece only needs a couple of xors to update the hash.
+
+use std::sync::OnceLock;
+
+use super::bitboard::HOLES;
+use super::{Board, Player};
+
+pub struct Keys {
+    pieces: [[u64; 6]; HOLES],
+    turn: [u64; 6],
+}
+
+impl Keys {
+    fn new() -> Self {
+        // fixed seed so hashes are the same from run to run
+        let mut state = 0x5EED_C4EC_4E25_u64;
+        let mut next = || splitmix64(&mut state);
+
+        let mut pieces = [[0; 6]; HOLES];
+        for hole in pieces.iter_mut() {
+            for key in hole.iter_mut() {
+                *key = next();
+            }
+        }
+        let mut turn = [0; 6];
+        for key in turn.iter_mut() {
+            *key = next();
+        }
+
+        Self { pieces, turn }
+    }
+
+    pub fn piece(&self, index: usize, player: Player) -> u64 {
+        self.pieces[index][player.index()]
+    }
+
+    pub fn turn(&self, player: Player) -> u64 {
+        self.turn[player.index()]
+    }
+}
+
+pub fn keys() -> &'static Keys {
+    static KEYS: OnceLock<Keys> = OnceLock::new();
+    KEYS.get_or_init(Keys::new)
+}
+
+/// The next number of the SplitMix64 sequence at `state`, which the hash
+/// keys and the bots' dice both come from.
+pub(crate) fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+impl Board {
+    /// The Zobrist hash of the pieces on the board and the side to move.
+    pub fn hash(&self) -> u64 {
+        self.piece_hash ^ keys().turn(self.turn)
+    }
+
+    /// How many times the current position, including the side to move, has
+    /// come up since the game was set up.
+    pub fn repetitions(&self) -> usize {
+        let hash = self.hash();
+        self.position_hashes
+            .iter()
+            .filter(|&&past_hash| past_hash == hash)
+            .count()
+    }
+}
@@ -0,0 +1,53 @@
+use std::time::Instant;
+
+use chinese_checkers::board::Board;
+
+const USAGE: &str = "\
+usage:
+    chinese_checkers                          start the game
+    chinese_checkers perft <players> <depth>  count the positions reachable in up to <depth> moves
+
+<players> is either a number of players, starting from the usual position,
+or a position string such as \"A/AA/AAA/AAAA/13/12/11/10/9/10/11/12/13/DDDD/DDD/DD/D AD A\"";
+
+/// What the command line asked for.
+pub enum Command {
+    Play,
+    Perft { board: Box<Board>, depth: usize },
+}
+
+impl Command {
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        match args {
+            [] => Ok(Self::Play),
+            [subcommand, players, depth] if subcommand == "perft" => Ok(Self::Perft {
+                board: Box::new(parse_board(players)?),
+                depth: parse_number("depth", depth)?,
+            }),
+            _ => Err(USAGE.to_string()),
+        }
+    }
+}
+
+fn parse_number(name: &str, arg: &str) -> Result<usize, String> {
+    arg.parse()
+        .map_err(|_| format!("<{}> must be a number, got {:?}\n{}", name, arg, USAGE))
+}
+
+/// The start of a game for a number of players, or a position string.
+fn parse_board(arg: &str) -> Result<Board, String> {
+    match arg.parse() {
+        Ok(players_count) => Board::new(players_count).map_err(|err| err.to_string()),
+        Err(_) => Board::from_position_string(arg)
+            .map_err(|err| format!("<players> isn't a valid position: {}\n{}", err, USAGE)),
+    }
+}
+
+pub fn perft(board: &Board, depth: usize) -> Result<(), String> {
+    for depth in 1..=depth {
+        let start = Instant::now();
+        let count = board.perft(depth);
+        println!("perft({}) = {} in {:.2?}", depth, count, start.elapsed());
+    }
+    Ok(())
+}
@@ -0,0 +1,17 @@
+//! Searching for good moves.
+
+mod analysis;
+mod bots;
+mod eval;
+mod mcts;
+mod search;
+mod solitaire;
+mod solver;
+mod tt;
+pub use analysis::{analyze, Analysis, MoveReview, SideReport, Verdict, BLUNDER_DISTANCE};
+pub use bots::{Bot, BotKind};
+pub use eval::{evaluate, evaluate_side, progress, relative_score, win_chances, Evaluation};
+pub use search::Search;
+pub use solitaire::{solitaire_route, solve_solitaire, Route, SolitaireSolution};
+pub use solver::{can_finish_within, finishing_moves, goal_entries, longest_jumps};
+pub use tt::{Bound, Entry, TranspositionTable};
@@ -0,0 +1,162 @@
+//! Looking back over a finished game to see where each side went wrong.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::Serialize;
+
+use super::Search;
+use crate::board::{Board, Move, Player, Spot};
+
+/// a move ending up this many steps further from the goal than the best
+/// move is a blunder
+pub const BLUNDER_DISTANCE: i32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Verdict {
+    /// the move the engine would have played
+    Best,
+    /// a different move that got at least as close to the goal
+    Good,
+    Inaccuracy,
+    Blunder,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct MoveReview {
+    /// counting from 1
+    pub number: usize,
+    /// the side whose piece was moved
+    pub side: Player,
+    pub played: Move,
+    pub best: Move,
+    /// how many more steps the side was left from its goal than after the
+    /// best move, see `Board::distance_to_goal`
+    pub distance_lost: i32,
+    pub verdict: Verdict,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct SideReport {
+    pub moves: usize,
+    pub best_moves: usize,
+    pub inaccuracies: usize,
+    pub blunders: usize,
+    /// the percentage of moves that lost no distance to the best move
+    pub accuracy: f32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Analysis {
+    pub moves: Vec<MoveReview>,
+    pub sides: BTreeMap<Player, SideReport>,
+}
+
+impl Analysis {
+    pub fn blunders(&self) -> impl Iterator<Item = &MoveReview> {
+        self.moves
+            .iter()
+            .filter(|review| review.verdict == Verdict::Blunder)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("the analysis only holds plain data")
+    }
+}
+
+impl fmt::Display for Analysis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (side, report) in &self.sides {
+            writeln!(
+                f,
+                "{:?}: {:.0}% accuracy over {} moves, {} best, {} inaccuracies, {} blunders",
+                side,
+                report.accuracy,
+                report.moves,
+                report.best_moves,
+                report.inaccuracies,
+                report.blunders
+            )?;
+        }
+        for review in self.blunders() {
+            writeln!(
+                f,
+                "{}. {} lost {} steps, best was {} -> {}",
+                review.number,
+                review.played,
+                review.distance_lost,
+                review.best.start,
+                review.best.end
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Replays the moves of `board` from the start, comparing each with the
+/// move a search `depth` moves deep finds.
+pub fn analyze(board: &Board, depth: u8) -> Analysis {
+    let mut replay = board.clone();
+    replay
+        .setup_players()
+        .expect("the board was already set up for the game");
+    let mut search = Search::new(depth);
+    let mut analysis = Analysis::default();
+
+    for (index, &played) in board.history.iter().enumerate() {
+        let side = match replay.get(&played.start) {
+            Some(&Spot::Player(side)) => side,
+            _ => played.player,
+        };
+        let (best_start, best_end) = search
+            .best_move(&replay)
+            .unwrap_or((played.start, played.end));
+        let best = Move {
+            player: played.player,
+            start: best_start,
+            end: best_end,
+        };
+
+        let distance_after = |(start, end)| {
+            let mut child = replay.clone();
+            child.make_move(start, end);
+            child.distance_to_goal(side)
+        };
+        let distance_lost = (distance_after((played.start, played.end))
+            - distance_after((best.start, best.end)))
+        .max(0);
+        let verdict = if (played.start, played.end) == (best.start, best.end) {
+            Verdict::Best
+        } else if distance_lost == 0 {
+            Verdict::Good
+        } else if distance_lost < BLUNDER_DISTANCE {
+            Verdict::Inaccuracy
+        } else {
+            Verdict::Blunder
+        };
+
+        let report = analysis.sides.entry(side).or_default();
+        report.moves += 1;
+        match verdict {
+            Verdict::Best => report.best_moves += 1,
+            Verdict::Good => {}
+            Verdict::Inaccuracy => report.inaccuracies += 1,
+            Verdict::Blunder => report.blunders += 1,
+        }
+        analysis.moves.push(MoveReview {
+            number: index + 1,
+            side,
+            played,
+            best,
+            distance_lost,
+            verdict,
+        });
+        replay.make_move(played.start, played.end);
+    }
+
+    for report in analysis.sides.values_mut() {
+        let accurate = report.moves - report.inaccuracies - report.blunders;
+        report.accuracy = 100.0 * accurate as f32 / report.moves.max(1) as f32;
+    }
+    analysis
+}
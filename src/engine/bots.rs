@@ -0,0 +1,142 @@
+//! Computer players of different strengths, picked by name so they can be
+//! set from the command line.
+
+use std::fmt;
+use std::str::FromStr;
+
+use super::mcts::Mcts;
+use super::search::forward_gain;
+use super::Search;
+use crate::board::{splitmix64, Board, HexCoord};
+
+/// Something that picks moves for whoever's turn it is.
+pub trait Bot {
+    /// The move to play, `None` if there is nothing to play.
+    fn choose_move(&mut self, board: &Board) -> Option<(HexCoord, HexCoord)>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BotKind {
+    /// any legal move
+    Random,
+    /// the move that gets a piece furthest towards the goal
+    Greedy,
+    /// a `Search` this many moves deep
+    Minimax(u8),
+    /// Monte Carlo tree search with this many playouts a move
+    Mcts(usize),
+}
+
+impl BotKind {
+    /// A new bot of this kind. Bots that make random choices make the same
+    /// ones for the same `seed`.
+    pub fn build(self, seed: u64) -> Box<dyn Bot> {
+        match self {
+            Self::Random => Box::new(RandomBot(Rng::new(seed))),
+            Self::Greedy => Box::new(GreedyBot(Rng::new(seed))),
+            Self::Minimax(depth) => Box::new(Search::new(depth)),
+            Self::Mcts(playouts) => Box::new(Mcts::new(playouts, Rng::new(seed))),
+        }
+    }
+}
+
+impl fmt::Display for BotKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Random => write!(f, "random"),
+            Self::Greedy => write!(f, "greedy"),
+            Self::Minimax(depth) => write!(f, "minimax:{}", depth),
+            Self::Mcts(playouts) => write!(f, "mcts:{}", playouts),
+        }
+    }
+}
+
+/// The number after the colon in a bot's name, `default` if there is none.
+/// Numbers that don't fit in `T` are refused rather than wrapped.
+fn parse_setting<T: FromStr>(kind: &str, setting: Option<&str>, default: T) -> Result<T, String> {
+    match setting {
+        Some(setting) => setting
+            .parse()
+            .map_err(|_| format!("bad setting {:?} for bot {:?}", setting, kind)),
+        None => Ok(default),
+    }
+}
+
+impl FromStr for BotKind {
+    type Err = String;
+
+    /// Parses the names `Display` gives, e.g. `minimax:2`.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let (kind, setting) = match name.split_once(':') {
+            Some((kind, setting)) => (kind, Some(setting)),
+            None => (name, None),
+        };
+        match kind {
+            "random" if setting.is_none() => Ok(Self::Random),
+            "greedy" if setting.is_none() => Ok(Self::Greedy),
+            "minimax" => Ok(Self::Minimax(parse_setting(kind, setting, 2)?)),
+            "mcts" => Ok(Self::Mcts(parse_setting(kind, setting, 200)?)),
+            _ => Err(format!("unknown bot {:?}", name)),
+        }
+    }
+}
+
+impl Bot for Search {
+    fn choose_move(&mut self, board: &Board) -> Option<(HexCoord, HexCoord)> {
+        self.best_move(board)
+    }
+}
+
+struct RandomBot(Rng);
+
+impl Bot for RandomBot {
+    fn choose_move(&mut self, board: &Board) -> Option<(HexCoord, HexCoord)> {
+        self.0.choose(&board.legal_moves())
+    }
+}
+
+struct GreedyBot(Rng);
+
+impl Bot for GreedyBot {
+    fn choose_move(&mut self, board: &Board) -> Option<(HexCoord, HexCoord)> {
+        greedy_move(board, &mut self.0)
+    }
+}
+
+/// One of the moves with the most `forward_gain`.
+pub(super) fn greedy_move(board: &Board, rng: &mut Rng) -> Option<(HexCoord, HexCoord)> {
+    let gain = forward_gain(board);
+    let moves = board.legal_moves();
+    let best_gain = moves.iter().map(&gain).max()?;
+    let best_moves: Vec<_> = moves
+        .into_iter()
+        .filter(|legal_move| gain(legal_move) == best_gain)
+        .collect();
+    rng.choose(&best_moves)
+}
+
+/// A small, seedable random number generator (splitmix64).
+#[derive(Debug, Clone)]
+pub(super) struct Rng(u64);
+
+impl Rng {
+    pub(super) fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub(super) fn next_u64(&mut self) -> u64 {
+        splitmix64(&mut self.0)
+    }
+
+    /// A number in `0..1`.
+    pub(super) fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    pub(super) fn choose<T: Copy>(&mut self, items: &[T]) -> Option<T> {
+        if items.is_empty() {
+            return None;
+        }
+        Some(items[(self.next_u64() % items.len() as u64) as usize])
+    }
+}
@@ -0,0 +1,151 @@
+use std::collections::BTreeMap;
+
+use crate::board::bitboard;
+use crate::board::{Board, HexCoord, Player, Spot};
+
+const DISTANCE_WEIGHT: i32 = 10;
+const STRAGGLER_WEIGHT: i32 = 6;
+const BLOCKED_WEIGHT: i32 = 8;
+const MOBILITY_WEIGHT: i32 = 1;
+/// pieces this many steps further from the goal than the average are
+/// stragglers
+const STRAGGLER_SLACK: i32 = 4;
+/// how many score points make one player about e times likelier to win
+const WIN_CHANCE_SCALE: f32 = 60.0;
+
+/// How well a side is doing, higher `score` being better.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Evaluation {
+    /// steps left to fill the goal, see `Board::distance_to_goal`
+    pub distance: i32,
+    /// steps the pieces left behind trail the rest by
+    pub stragglers: i32,
+    /// goal holes taken by other sides' pieces
+    pub blocked: i32,
+    /// moves the side could make if it were their turn
+    pub mobility: i32,
+    pub score: i32,
+}
+
+pub fn evaluate_side(board: &Board, player: Player) -> Evaluation {
+    let distance = board.distance_to_goal(player);
+
+    let pieces = board.pieces(player);
+    let goal = board.goal(player);
+    let steps_left: Vec<i32> = pieces.iter().map(|&piece| steps_to(piece, &goal)).collect();
+    let average = steps_left.iter().sum::<i32>() / steps_left.len().max(1) as i32;
+    let stragglers = steps_left
+        .iter()
+        .map(|&steps| (steps - average - STRAGGLER_SLACK).max(0))
+        .sum();
+
+    let blocked = goal
+        .iter()
+        .filter(|coord| matches!(board.get(coord), Some(Spot::Player(other)) if *other != player))
+        .count() as i32;
+
+    let occupied = board.occupied();
+    let mobility = board
+        .piece_set(player)
+        .iter()
+        .map(|start| bitboard::destinations(start, occupied).len() as i32)
+        .sum();
+
+    let score =
+        -DISTANCE_WEIGHT * distance - STRAGGLER_WEIGHT * stragglers - BLOCKED_WEIGHT * blocked
+            + MOBILITY_WEIGHT * mobility;
+
+    Evaluation {
+        distance,
+        stragglers,
+        blocked,
+        mobility,
+        score,
+    }
+}
+
+pub fn evaluate(board: &Board) -> BTreeMap<Player, Evaluation> {
+    board
+        .players
+        .iter()
+        .map(|&player| (player, evaluate_side(board, player)))
+        .collect()
+}
+
+/// `player`'s score less that of their strongest opponent, the number a
+/// search tries to maximize.
+pub fn relative_score(board: &Board, player: Player) -> i32 {
+    let team = board.team_of(player);
+    let evaluations = evaluate(board);
+    let own = team
+        .iter()
+        .filter_map(|member| evaluations.get(member))
+        .map(|evaluation| evaluation.score)
+        .max()
+        .unwrap_or(0);
+    let best_opponent = evaluations
+        .iter()
+        .filter(|(side, _evaluation)| !team.contains(side))
+        .map(|(_side, evaluation)| evaluation.score)
+        .max()
+        .unwrap_or(0);
+    own - best_opponent
+}
+
+/// How far along `player` is, from 0 at the start to 1 once finished.
+pub fn progress(board: &Board, player: Player) -> f32 {
+    let start_distance = start_distance(board, player);
+    if start_distance == 0 {
+        return 1.0;
+    }
+    1.0 - board.distance_to_goal(player) as f32 / start_distance as f32
+}
+
+/// The distance to goal of `player` with all pieces still at home.
+fn start_distance(board: &Board, player: Player) -> i32 {
+    let goal = board.goal(player);
+    let home = player.home();
+    let steps_to_tip = |coords: &[HexCoord]| -> i32 {
+        match goal
+            .iter()
+            .max_by_key(|coord| coord.distance(HexCoord::default()))
+        {
+            Some(&tip) => coords.iter().map(|coord| coord.distance(tip)).sum(),
+            None => 0,
+        }
+    };
+    steps_to_tip(&home) - steps_to_tip(&goal)
+}
+
+/// The steps from `coord` to the nearest hole of `goal`.
+fn steps_to(coord: HexCoord, goal: &[HexCoord]) -> i32 {
+    goal.iter()
+        .map(|&goal_coord| coord.distance(goal_coord))
+        .min()
+        .unwrap_or(0)
+}
+
+/// Each player's estimated chance of winning, from how their scores
+/// compare. Once someone has won it's certain.
+pub fn win_chances(board: &Board) -> BTreeMap<Player, f32> {
+    let evaluations = evaluate(board);
+    if let Some(winner) = board.outcome.winner() {
+        return evaluations
+            .keys()
+            .map(|&player| (player, if player == winner { 1.0 } else { 0.0 }))
+            .collect();
+    }
+
+    let scaled = |evaluation: &Evaluation| evaluation.score as f32 / WIN_CHANCE_SCALE;
+    let max = evaluations.values().map(scaled).fold(f32::MIN, f32::max);
+    let weights: BTreeMap<Player, f32> = evaluations
+        .iter()
+        .map(|(&player, evaluation)| (player, (scaled(evaluation) - max).exp()))
+        .collect();
+    let total: f32 = weights.values().sum();
+
+    weights
+        .into_iter()
+        .map(|(player, weight)| (player, weight / total))
+        .collect()
+}
@@ -0,0 +1,146 @@
+//! Monte Carlo tree search: grows a tree of the most promising moves by
+//! playing many short, mostly greedy games from the current position.
+
+use std::collections::BTreeMap;
+
+use super::bots::{greedy_move, Bot, Rng};
+use super::win_chances;
+use crate::board::{Board, HexCoord, Player};
+
+/// how often the moves tried less often get explored
+const EXPLORATION: f32 = 0.7;
+/// moves played per side in a playout before the position is scored
+const PLAYOUT_ROUNDS: usize = 3;
+/// how often a playout plays a random move instead of a greedy one
+const RANDOM_MOVE_CHANCE: f32 = 0.2;
+
+pub struct Mcts {
+    playouts: usize,
+    rng: Rng,
+}
+
+struct Node {
+    /// the move leading here and the side that made it
+    played: Option<((HexCoord, HexCoord), Player)>,
+    children: Vec<usize>,
+    untried: Vec<(HexCoord, HexCoord)>,
+    visits: u32,
+    /// the summed chances of winning for the side that made `played`
+    reward: f32,
+}
+
+impl Node {
+    fn new(board: &Board, played: Option<((HexCoord, HexCoord), Player)>) -> Self {
+        Self {
+            played,
+            children: Vec::new(),
+            untried: if board.is_over() {
+                Vec::new()
+            } else {
+                board.legal_moves()
+            },
+            visits: 0,
+            reward: 0.0,
+        }
+    }
+}
+
+impl Mcts {
+    pub(super) fn new(playouts: usize, rng: Rng) -> Self {
+        Self { playouts, rng }
+    }
+
+    fn select_child(&self, nodes: &[Node], parent: usize) -> usize {
+        let log_visits = (nodes[parent].visits.max(1) as f32).ln();
+        let uct = |child: &Node| {
+            child.reward / child.visits as f32
+                + EXPLORATION * (log_visits / child.visits as f32).sqrt()
+        };
+        *nodes[parent]
+            .children
+            .iter()
+            .max_by(|&&first, &&second| uct(&nodes[first]).total_cmp(&uct(&nodes[second])))
+            .expect("only called on nodes with children")
+    }
+
+    fn playout(&mut self, board: &mut Board) -> BTreeMap<Player, f32> {
+        for _ in 0..PLAYOUT_ROUNDS * board.players.len() {
+            if board.is_over() {
+                break;
+            }
+            let next_move = if self.rng.next_f32() < RANDOM_MOVE_CHANCE {
+                self.rng.choose(&board.legal_moves())
+            } else {
+                greedy_move(board, &mut self.rng)
+            };
+            match next_move {
+                Some((start, end)) => board.make_move(start, end),
+                None => break,
+            }
+        }
+
+        let chances = win_chances(board);
+        board
+            .players
+            .iter()
+            .map(|&player| {
+                let team_chance = board
+                    .team_of(player)
+                    .iter()
+                    .filter_map(|member| chances.get(member))
+                    .sum();
+                (player, team_chance)
+            })
+            .collect()
+    }
+}
+
+impl Bot for Mcts {
+    fn choose_move(&mut self, board: &Board) -> Option<(HexCoord, HexCoord)> {
+        let mut nodes = vec![Node::new(board, None)];
+        match nodes[0].untried.len() {
+            0 => return None,
+            1 => return nodes[0].untried.pop(),
+            _ => {}
+        }
+
+        for _ in 0..self.playouts {
+            let mut board = board.clone();
+            let mut path = vec![0];
+            let mut node = 0;
+            while nodes[node].untried.is_empty() && !nodes[node].children.is_empty() {
+                node = self.select_child(&nodes, node);
+                let ((start, end), _mover) = nodes[node].played.expect("children have a move");
+                board.make_move(start, end);
+                path.push(node);
+            }
+
+            if !nodes[node].untried.is_empty() {
+                let index = (self.rng.next_u64() % nodes[node].untried.len() as u64) as usize;
+                let (start, end) = nodes[node].untried.swap_remove(index);
+                let mover = board.turn;
+                board.make_move(start, end);
+                nodes.push(Node::new(&board, Some(((start, end), mover))));
+                let child = nodes.len() - 1;
+                nodes[node].children.push(child);
+                path.push(child);
+            }
+
+            let rewards = self.playout(&mut board);
+            for &node in &path {
+                let node = &mut nodes[node];
+                node.visits += 1;
+                if let Some((_played, mover)) = node.played {
+                    node.reward += rewards.get(&mover).copied().unwrap_or(0.0);
+                }
+            }
+        }
+
+        nodes[0]
+            .children
+            .iter()
+            .max_by_key(|&&child| nodes[child].visits)
+            .and_then(|&child| nodes[child].played)
+            .map(|(played, _mover)| played)
+    }
+}
@@ -0,0 +1,169 @@
+use std::collections::BTreeMap;
+
+use super::{relative_score, Bound, Entry, TranspositionTable};
+use crate::board::{Board, HexCoord, Player, Spot};
+
+const TT_BITS: u32 = 18;
+const WIN_SCORE: i32 = 1_000_000;
+
+/// Looks a fixed number of moves ahead for the move that does best for the
+/// player to move, assuming everyone else plays against them.
+#[derive(Debug, Clone)]
+pub struct Search {
+    pub depth: u8,
+    tt: TranspositionTable,
+    nodes: u64,
+}
+
+impl Search {
+    pub fn new(depth: u8) -> Self {
+        Self {
+            depth,
+            tt: TranspositionTable::new(TT_BITS),
+            nodes: 0,
+        }
+    }
+
+    /// The positions looked at by the last `best_move`.
+    pub fn nodes(&self) -> u64 {
+        self.nodes
+    }
+
+    pub fn best_move(&mut self, board: &Board) -> Option<(HexCoord, HexCoord)> {
+        self.nodes = 0;
+        if board.is_over() {
+            return None;
+        }
+        let (_score, best_move) =
+            self.alpha_beta(board, board.turn, self.depth.max(1), -WIN_SCORE, WIN_SCORE);
+        best_move
+    }
+
+    /// How well the player to move stands, as seen `depth` moves ahead.
+    pub fn score(&mut self, board: &Board) -> i32 {
+        self.nodes = 0;
+        self.alpha_beta(board, board.turn, self.depth, -WIN_SCORE, WIN_SCORE)
+            .0
+    }
+
+    fn alpha_beta(
+        &mut self,
+        board: &Board,
+        root: Player,
+        depth: u8,
+        mut alpha: i32,
+        mut beta: i32,
+    ) -> (i32, Option<(HexCoord, HexCoord)>) {
+        self.nodes += 1;
+        if depth == 0 || board.is_over() {
+            return (leaf_score(board, root), None);
+        }
+
+        // scores are from `root`'s point of view, so keep roots apart
+        let hash = board.hash() ^ (root.index() as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        let tt_move = match self.tt.probe(hash) {
+            Some(entry) if entry.depth >= depth => match entry.bound {
+                Bound::Exact => return (entry.score, entry.best_move),
+                Bound::Lower if entry.score >= beta => return (entry.score, entry.best_move),
+                Bound::Upper if entry.score <= alpha => return (entry.score, entry.best_move),
+                _ => entry.best_move,
+            },
+            Some(entry) => entry.best_move,
+            None => None,
+        };
+
+        let (original_alpha, original_beta) = (alpha, beta);
+        let maximizing = board.team_of(board.turn).contains(&root);
+        let mut best_score = if maximizing { -WIN_SCORE } else { WIN_SCORE };
+        let mut best_move = None;
+        for (start, end) in ordered_moves(board, tt_move) {
+            let mut child = board.clone();
+            child.make_move(start, end);
+            let (score, _) = self.alpha_beta(&child, root, depth - 1, alpha, beta);
+
+            if maximizing {
+                if score > best_score || best_move.is_none() {
+                    best_score = score;
+                    best_move = Some((start, end));
+                }
+                alpha = alpha.max(score);
+            } else {
+                if score < best_score || best_move.is_none() {
+                    best_score = score;
+                    best_move = Some((start, end));
+                }
+                beta = beta.min(score);
+            }
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        let bound = if best_score <= original_alpha {
+            Bound::Upper
+        } else if best_score >= original_beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        self.tt.store(Entry {
+            hash,
+            depth,
+            score: best_score,
+            bound,
+            best_move,
+        });
+        (best_score, best_move)
+    }
+}
+
+fn leaf_score(board: &Board, root: Player) -> i32 {
+    let team = board.team_of(root);
+    match board.outcome.winning_team() {
+        Some(winners) if winners == &team => WIN_SCORE - 1,
+        Some(_) => -WIN_SCORE + 1,
+        None if board.outcome.draw.is_some() => 0,
+        None => relative_score(board, root),
+    }
+}
+
+/// The legal moves, the one remembered from earlier searches first and then
+/// those that get furthest towards the mover's goal.
+fn ordered_moves(
+    board: &Board,
+    tt_move: Option<(HexCoord, HexCoord)>,
+) -> Vec<(HexCoord, HexCoord)> {
+    let gain = forward_gain(board);
+    let mut moves = board.legal_moves();
+    moves.sort_by_key(|legal_move| -gain(legal_move));
+    if let Some(tt_move) = tt_move {
+        if let Some(position) = moves.iter().position(|&legal_move| legal_move == tt_move) {
+            let tt_move = moves.remove(position);
+            moves.insert(0, tt_move);
+        }
+    }
+    moves
+}
+
+/// How many steps a move brings the moved piece closer to the tip of its
+/// side's goal.
+pub(super) fn forward_gain(board: &Board) -> impl Fn(&(HexCoord, HexCoord)) -> i32 + '_ {
+    let tips: BTreeMap<Player, HexCoord> = board
+        .movable_players()
+        .into_iter()
+        .filter_map(|player| {
+            let tip = board
+                .goal(player)
+                .into_iter()
+                .max_by_key(|coord| coord.distance(HexCoord::default()))?;
+            Some((player, tip))
+        })
+        .collect();
+    move |&(start, end)| match board.get(&start) {
+        Some(Spot::Player(player)) => match tips.get(player) {
+            Some(&tip) => start.distance(tip) - end.distance(tip),
+            None => 0,
+        },
+        _ => 0,
+    }
+}
@@ -0,0 +1,438 @@
+//! Crossing the board alone: the quickest way the engine finds for one side
+//! to fill its goal triangle while every other piece stays put, and how few
+//! moves it can possibly take.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::board::bitboard::{self, geometry, Bitset};
+use crate::board::{Board, HexCoord, Player};
+
+/// how many values each of the three hex axes takes across the board
+const AXIS_VALUES: usize = 17;
+
+/// A route found for a side crossing the board alone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    pub moves: Vec<(HexCoord, HexCoord)>,
+}
+
+/// A route across, and the fewest moves any route could take.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolitaireSolution {
+    pub route: Route,
+    /// no route takes fewer moves than this
+    pub lower_bound: usize,
+}
+
+impl SolitaireSolution {
+    /// Whether no route is shorter than `route`.
+    pub fn is_optimal(&self) -> bool {
+        self.route.moves.len() <= self.lower_bound
+    }
+}
+
+/// one position of the beam, with how it was reached
+struct Node {
+    pieces: Bitset,
+    /// the index into the previous layer and the move from there
+    came_from: Option<(usize, (usize, usize))>,
+}
+
+/// The pieces of `side`, the holes of its goal and every other piece on the
+/// board, if the goal can be filled at all.
+fn crossing(board: &Board, side: Player) -> Option<(Bitset, Bitset, Bitset)> {
+    let geometry = geometry();
+    let goal = board
+        .goal(side)
+        .into_iter()
+        .filter_map(|coord| geometry.index(coord))
+        .fold(Bitset::EMPTY, |set, index| set | Bitset::single(index));
+    let start = board.piece_set(side);
+    let obstacles = Bitset(board.occupied().0 & !start.0);
+    if start.len() != goal.len() || !(obstacles & goal).is_empty() {
+        return None;
+    }
+    Some((start, goal, obstacles))
+}
+
+/// The fewest moves the engine finds for `side` to fill its goal triangle
+/// on its own, with every other piece on the board staying where it is.
+///
+/// After each move only the `width` positions closest to the goal are kept,
+/// so wider searches find shorter routes but take longer. The route is not
+/// always the shortest possible one, see `solve_solitaire`. `None` if the
+/// goal can't be filled, e.g. because other pieces sit in it.
+pub fn solitaire_route(board: &Board, side: Player, width: usize) -> Option<Route> {
+    let (start, goal, obstacles) = crossing(board, side)?;
+    let steps = steps_to_tip(board, side)?;
+    beam_search(start, goal, obstacles, width, |pieces| {
+        pieces.iter().map(|index| steps[index]).sum()
+    })
+}
+
+/// The steps from every hole to the tip of `side`'s goal, far from the
+/// center.
+fn steps_to_tip(board: &Board, side: Player) -> Option<Vec<i32>> {
+    let center = HexCoord::default();
+    let tip = board
+        .goal(side)
+        .into_iter()
+        .max_by_key(|coord| coord.distance(center))?;
+    Some(
+        geometry()
+            .coords()
+            .iter()
+            .map(|coord| coord.distance(tip))
+            .collect(),
+    )
+}
+
+/// Moves the pieces from `start` to `goal`, keeping the `width` positions
+/// with the lowest `distance` after each move.
+fn beam_search(
+    start: Bitset,
+    goal: Bitset,
+    obstacles: Bitset,
+    width: usize,
+    distance: impl Fn(Bitset) -> i32,
+) -> Option<Route> {
+    let mut seen = HashSet::new();
+    seen.insert(start);
+    let mut layers = vec![vec![Node {
+        pieces: start,
+        came_from: None,
+    }]];
+    // no sensible route is anywhere near this long
+    let max_moves = 20 * goal.len();
+    for _ in 0..max_moves {
+        let layer = layers.last()?;
+        let mut next_layer: Vec<(i32, Node)> = Vec::new();
+        for (node_index, node) in layer.iter().enumerate() {
+            let occupied = node.pieces | obstacles;
+            for from in node.pieces.iter() {
+                for to in bitboard::destinations(from, occupied).iter() {
+                    let mut pieces = node.pieces;
+                    pieces.remove(from);
+                    pieces.insert(to);
+                    if !seen.insert(pieces) {
+                        continue;
+                    }
+                    let next = Node {
+                        pieces,
+                        came_from: Some((node_index, (from, to))),
+                    };
+                    if pieces == goal {
+                        layers.push(vec![next]);
+                        return Some(trace_route(&layers));
+                    }
+                    next_layer.push((distance(pieces), next));
+                }
+            }
+        }
+        if next_layer.is_empty() {
+            return None;
+        }
+        next_layer.sort_by_key(|(distance, _)| *distance);
+        next_layer.truncate(width.max(1));
+        layers.push(next_layer.into_iter().map(|(_, node)| node).collect());
+    }
+    None
+}
+
+/// The moves leading to the single position of the last layer.
+fn trace_route(layers: &[Vec<Node>]) -> Route {
+    let geometry = geometry();
+    let mut moves = Vec::new();
+    let mut node_index = 0;
+    for layer in layers.iter().rev() {
+        if let Some((previous, (from, to))) = layer[node_index].came_from {
+            moves.push((geometry.coord(from), geometry.coord(to)));
+            node_index = previous;
+        }
+    }
+    moves.reverse();
+    Route { moves }
+}
+
+/// The shortest route the engine finds for `side` searching `width`
+/// positions wide, and the fewest moves crossing can take.
+///
+/// Besides `solitaire_route`, a second beam search keeps the positions a
+/// `RowRelaxation` rates closest to the goal, which finds shorter routes
+/// on some boards. The lower bound is then raised by an iterative
+/// deepening search, which finds the shortest route once it reaches its
+/// length. As every move allowed makes that search many times longer, it
+/// gives up after visiting `max_nodes` positions, and the route is then
+/// only known to be optimal if it's as short as the bound. `None` if the
+/// goal can't be filled.
+pub fn solve_solitaire(
+    board: &Board,
+    side: Player,
+    width: usize,
+    max_nodes: u64,
+) -> Option<SolitaireSolution> {
+    let route = solitaire_route(board, side, width)?;
+    let (start, goal, obstacles) = crossing(board, side)?;
+    let steps = steps_to_tip(board, side)?;
+    let relaxation = RowRelaxation::new(start, goal, obstacles, route.moves.len());
+    // the relaxation is coarse, so the steps break its many ties
+    let relaxed_route = beam_search(start, goal, obstacles, width, |pieces| {
+        let moves_left = relaxation.moves_left(pieces) as i32;
+        let steps: i32 = pieces.iter().map(|index| steps[index]).sum();
+        moves_left * 1000 + steps
+    });
+    let route = match relaxed_route {
+        Some(relaxed_route) if relaxed_route.moves.len() < route.moves.len() => relaxed_route,
+        _ => route,
+    };
+    let longest = route.moves.len();
+
+    let mut search = Deepening {
+        relaxation: &relaxation,
+        goal,
+        obstacles,
+        visited: HashMap::new(),
+        moves: Vec::new(),
+        nodes: 0,
+        max_nodes,
+    };
+    let mut lower_bound = relaxation.moves_left(start);
+    while lower_bound < longest {
+        search.visited.clear();
+        match search.search(start, 0, lower_bound) {
+            Searched::Found => {
+                let geometry = geometry();
+                let moves = search
+                    .moves
+                    .iter()
+                    .map(|&(from, to)| (geometry.coord(from), geometry.coord(to)))
+                    .collect();
+                return Some(SolitaireSolution {
+                    route: Route { moves },
+                    lower_bound,
+                });
+            }
+            Searched::NotFound => lower_bound += 1,
+            Searched::OutOfNodes => break,
+        }
+    }
+    Some(SolitaireSolution { route, lower_bound })
+}
+
+enum Searched {
+    Found,
+    NotFound,
+    OutOfNodes,
+}
+
+/// An iterative deepening search for the shortest route, guided by the
+/// lower bound of a `RowRelaxation`.
+struct Deepening<'a> {
+    relaxation: &'a RowRelaxation,
+    goal: Bitset,
+    obstacles: Bitset,
+    /// the fewest moves each position was reached in, this iteration
+    visited: HashMap<Bitset, usize>,
+    /// the moves to the position being searched, as hole indices
+    moves: Vec<(usize, usize)>,
+    nodes: u64,
+    max_nodes: u64,
+}
+
+impl Deepening<'_> {
+    /// Looks for a route through `pieces`, reached in `moves_made` moves,
+    /// that's at most `bound` moves long.
+    fn search(&mut self, pieces: Bitset, moves_made: usize, bound: usize) -> Searched {
+        if pieces == self.goal {
+            return Searched::Found;
+        }
+        if moves_made + self.relaxation.moves_left(pieces) > bound {
+            return Searched::NotFound;
+        }
+        if self.nodes >= self.max_nodes {
+            return Searched::OutOfNodes;
+        }
+        self.nodes += 1;
+        match self.visited.get(&pieces) {
+            Some(&reached_in) if reached_in <= moves_made => return Searched::NotFound,
+            _ => {
+                self.visited.insert(pieces, moves_made);
+            }
+        }
+
+        let occupied = pieces | self.obstacles;
+        for from in pieces.iter() {
+            for to in bitboard::destinations(from, occupied).iter() {
+                let mut next = pieces;
+                next.remove(from);
+                next.insert(to);
+                self.moves.push((from, to));
+                match self.search(next, moves_made + 1, bound) {
+                    Searched::NotFound => {}
+                    searched => return searched,
+                }
+                self.moves.pop();
+            }
+        }
+        Searched::NotFound
+    }
+}
+
+/// The crossing seen along a single axis of the board, where a piece may
+/// jump over any piece one row ahead or behind it and a row holds any
+/// number of pieces. Every real move is a move here too, so the moves left
+/// here are never more than the real ones.
+struct RowRelaxation {
+    /// the row of every hole
+    rows: Vec<usize>,
+    pieces: usize,
+    binomials: Vec<Vec<u64>>,
+    /// the moves left from each way of spreading the pieces over the rows,
+    /// by `rank`, counted up to `max_moves`
+    moves_left: Vec<u8>,
+    max_moves: usize,
+}
+
+impl RowRelaxation {
+    /// Counts the moves left from every spread of `start.len()` pieces, up
+    /// to `max_moves`, along the axis `start` and `goal` are furthest apart
+    /// on.
+    fn new(start: Bitset, goal: Bitset, obstacles: Bitset, max_moves: usize) -> Self {
+        let geometry = geometry();
+        let axes: [fn(HexCoord) -> i32; 3] = [
+            |coord| coord.horz,
+            |coord| coord.slant,
+            |coord| -coord.horz - coord.slant,
+        ];
+        let sum = |set: Bitset, axis: fn(HexCoord) -> i32| -> i32 {
+            set.iter().map(|index| axis(geometry.coord(index))).sum()
+        };
+        let axis = *axes
+            .iter()
+            .max_by_key(|&&axis| (sum(goal, axis) - sum(start, axis)).abs())
+            .expect("there are three axes");
+        let half = AXIS_VALUES as i32 / 2;
+        let rows = geometry
+            .coords()
+            .iter()
+            .map(|&coord| (axis(coord) + half) as usize)
+            .collect();
+
+        let pieces = start.len();
+        let size = pieces + AXIS_VALUES;
+        let mut binomials = vec![vec![0; pieces + 1]; size];
+        for n in 0..size {
+            binomials[n][0] = 1;
+            for k in 1..=pieces.min(n) {
+                binomials[n][k] = binomials[n - 1][k - 1] + binomials[n - 1][k];
+            }
+        }
+        let spreads = binomials[size - 1][pieces] as usize;
+        let mut relaxation = Self {
+            rows,
+            pieces,
+            binomials,
+            moves_left: vec![u8::MAX; spreads],
+            max_moves: max_moves.min(usize::from(u8::MAX - 1)),
+        };
+        relaxation.count_moves(goal, obstacles);
+        relaxation
+    }
+
+    /// Goes breadth first from the goal, as moves here can be undone.
+    fn count_moves(&mut self, goal: Bitset, obstacles: Bitset) {
+        let goal = self.spread(goal);
+        let fixed = self.spread(obstacles);
+        let goal_rank = self.rank(&goal);
+        self.moves_left[goal_rank] = 0;
+        let mut queue = VecDeque::new();
+        queue.push_back((goal, 0));
+        while let Some((spread, moves)) = queue.pop_front() {
+            if moves >= self.max_moves {
+                continue;
+            }
+            for next in relaxed_moves(&spread, &fixed) {
+                let rank = self.rank(&next);
+                if self.moves_left[rank] == u8::MAX {
+                    self.moves_left[rank] = (moves + 1) as u8;
+                    queue.push_back((next, moves + 1));
+                }
+            }
+        }
+    }
+
+    /// How many pieces of `set` are in each row.
+    fn spread(&self, set: Bitset) -> [u8; AXIS_VALUES] {
+        let mut spread = [0; AXIS_VALUES];
+        for index in set.iter() {
+            spread[self.rows[index]] += 1;
+        }
+        spread
+    }
+
+    /// The index of `spread` among all spreads of as many pieces, counting
+    /// the pieces from the lowest row up in the combinatorial number system.
+    fn rank(&self, spread: &[u8; AXIS_VALUES]) -> usize {
+        let mut rank = 0;
+        let mut counted = 0;
+        for (row, &count) in spread.iter().enumerate() {
+            for _ in 0..count {
+                counted += 1;
+                rank += self.binomials[row + counted - 1][counted];
+            }
+        }
+        rank as usize
+    }
+
+    /// The fewest moves `pieces` could fill the goal in.
+    fn moves_left(&self, pieces: Bitset) -> usize {
+        if pieces.len() != self.pieces {
+            return 0;
+        }
+        match self.moves_left[self.rank(&self.spread(pieces))] {
+            u8::MAX => self.max_moves + 1,
+            moves => usize::from(moves),
+        }
+    }
+}
+
+/// The spreads one move away from `spread`, with the pieces in `fixed`
+/// only there to be jumped over.
+fn relaxed_moves(spread: &[u8; AXIS_VALUES], fixed: &[u8; AXIS_VALUES]) -> Vec<[u8; AXIS_VALUES]> {
+    let mut spreads = Vec::new();
+    for start in 0..AXIS_VALUES {
+        if spread[start] == 0 {
+            continue;
+        }
+        let mut rest = *spread;
+        rest[start] -= 1;
+        let can_jump_over = |row: usize| rest[row] + fixed[row] > 0;
+
+        let mut reached = [false; AXIS_VALUES];
+        reached[start] = true;
+        let mut jumps = vec![start];
+        while let Some(row) = jumps.pop() {
+            if row + 2 < AXIS_VALUES && can_jump_over(row + 1) && !reached[row + 2] {
+                reached[row + 2] = true;
+                jumps.push(row + 2);
+            }
+            if row >= 2 && can_jump_over(row - 1) && !reached[row - 2] {
+                reached[row - 2] = true;
+                jumps.push(row - 2);
+            }
+        }
+        if start + 1 < AXIS_VALUES {
+            reached[start + 1] = true;
+        }
+        if start >= 1 {
+            reached[start - 1] = true;
+        }
+
+        for end in (0..AXIS_VALUES).filter(|&end| end != start && reached[end]) {
+            let mut next = rest;
+            next[end] += 1;
+            spreads.push(next);
+        }
+    }
+    spreads
+}
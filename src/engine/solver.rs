@@ -0,0 +1,84 @@
+//! Exact answers for small questions about a position, such as the ones
+//! puzzles ask. The other sides never move while the side to move is
+//! being solved for.
+
+use crate::board::{Board, HexCoord, Player, Spot};
+
+/// The moves that take a piece of the side to move from outside its goal
+/// triangle into it.
+pub fn goal_entries(board: &Board) -> Vec<(HexCoord, HexCoord)> {
+    let goal = board.goal(board.turn);
+    board
+        .legal_moves()
+        .into_iter()
+        .filter(|(start, end)| !goal.contains(start) && goal.contains(end))
+        .collect()
+}
+
+/// The moves chaining together the most jumps, none if no piece can jump.
+pub fn longest_jumps(board: &Board) -> Vec<(HexCoord, HexCoord)> {
+    let jumps: Vec<((HexCoord, HexCoord), usize)> = board
+        .legal_moves()
+        .into_iter()
+        .map(|(start, end)| ((start, end), board.jump_count(start, end)))
+        .collect();
+    let most_jumps = jumps.iter().map(|&(_, count)| count).max().unwrap_or(0);
+    if most_jumps == 0 {
+        return Vec::new();
+    }
+    jumps
+        .into_iter()
+        .filter(|&(_, count)| count == most_jumps)
+        .map(|(played, _)| played)
+        .collect()
+}
+
+/// The moves after which the side to move can still fill its goal
+/// triangle within `moves` of its own moves, this one included.
+pub fn finishing_moves(board: &Board, moves: usize) -> Vec<(HexCoord, HexCoord)> {
+    if moves == 0 {
+        return Vec::new();
+    }
+    let side = board.turn;
+    board
+        .legal_moves()
+        .into_iter()
+        .filter(|&(start, end)| {
+            let after = play_alone(board, side, start, end);
+            can_finish_within(&after, side, moves - 1)
+        })
+        .collect()
+}
+
+/// Whether `side` can fill its goal triangle within `moves` of its own
+/// moves.
+pub fn can_finish_within(board: &Board, side: Player, moves: usize) -> bool {
+    if board.rules().has_finished(board, side) {
+        return true;
+    }
+    // every move fills at most one more hole of the goal
+    let unfilled = board
+        .goal(side)
+        .iter()
+        .filter(|coord| board.get(coord) != Some(&Spot::Player(side)))
+        .count();
+    if unfilled > moves || board.is_over() {
+        return false;
+    }
+
+    let mut board = board.clone();
+    board.turn = side;
+    board.legal_moves().into_iter().any(|(start, end)| {
+        let after = play_alone(&board, side, start, end);
+        can_finish_within(&after, side, moves - 1)
+    })
+}
+
+/// The board after `side` moves, with the turn handed straight back.
+fn play_alone(board: &Board, side: Player, start: HexCoord, end: HexCoord) -> Board {
+    let mut after = board.clone();
+    after.turn = side;
+    after.make_move(start, end);
+    after.turn = side;
+    after
+}
@@ -0,0 +1,61 @@
+use crate::board::HexCoord;
+
+/// How an entry's score relates to the true score of the position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bound {
+    Exact,
+    /// the true score is at least this (the search failed high)
+    Lower,
+    /// the true score is at most this (the search failed low)
+    Upper,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    /// the full `Board::hash` of the position, to tell apart positions that
+    /// share a slot
+    pub hash: u64,
+    pub depth: u8,
+    pub score: i32,
+    pub bound: Bound,
+    pub best_move: Option<(HexCoord, HexCoord)>,
+}
+
+/// A fixed size cache of search results, indexed by `Board::hash`.
+#[derive(Debug, Clone)]
+pub struct TranspositionTable {
+    entries: Vec<Option<Entry>>,
+}
+
+impl TranspositionTable {
+    /// A table with room for `2^bits` entries.
+    pub fn new(bits: u32) -> Self {
+        Self {
+            entries: vec![None; 1 << bits],
+        }
+    }
+
+    fn slot(&self, hash: u64) -> usize {
+        (hash as usize) & (self.entries.len() - 1)
+    }
+
+    pub fn probe(&self, hash: u64) -> Option<Entry> {
+        self.entries[self.slot(hash)].filter(|entry| entry.hash == hash)
+    }
+
+    /// Stores `entry`, unless its slot holds a deeper search of the same
+    /// position.
+    pub fn store(&mut self, entry: Entry) {
+        let slot = self.slot(entry.hash);
+        match self.entries[slot] {
+            Some(existing) if existing.hash == entry.hash && existing.depth > entry.depth => {}
+            _ => self.entries[slot] = Some(entry),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        for entry in &mut self.entries {
+            *entry = None;
+        }
+    }
+}
@@ -2,19 +2,87 @@ use coffee::graphics::{
     Color, CursorIcon, Frame, HorizontalAlignment, Mesh, Point, Shape, Transformation, Window,
     WindowSettings,
 };
+use coffee::load::{Join, Task};
 use coffee::ui::{
-    button, Align, Button, Checkbox, Column, Element, Justify, Renderer, Row, Text, UserInterface,
+    button, Align, Button, Checkbox, Column, Element, Justify, ProgressBar, Radio, Renderer, Row,
+    Text, UserInterface,
 };
-use coffee::{load::Task, Game, Result, Timer};
+use coffee::{Game, Result, Timer};
 
-use crate::board::{Board, HexCoord, Player, SideOfStar, Spot};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
 
+use crate::archive::{self, Archive, ArchivedGame, ARCHIVE_PATH};
+use crate::board::{
+    gen_seats, single_side_seats, AntiSpoiling, Board, HexCoord, Move, Player, SeatKind,
+    SideOfStar, Spot, TurnOrder,
+};
+use crate::engine::{self, BotKind, SolitaireSolution};
+use crate::profiles::{Profiles, MAX_NAME_LENGTH, PROFILES_PATH};
+use crate::puzzles::{Attempt, Attempted, Puzzle, PuzzleProgress, PUZZLE_PROGRESS_PATH};
+use crate::solitaire::{Challenge, Obstacles, SolitaireRecords, SOLITAIRE_RECORDS_PATH};
+use crate::sound::{Silent, SoundEvent, SoundSettings, Sounds, SOUND_SETTINGS_PATH};
+use crate::tutorial::{self, Lesson};
+
+mod assets;
 mod dragndrop;
+mod loading_screen;
+use assets::{load_sounds, Assets, Labels};
 use dragndrop::DragNDrop;
+use loading_screen::Splash;
 
 const SIN_30_DEG: f32 = 0.5;
-const COS_30_DEG: f32 = 0.866025403784439;
+const COS_30_DEG: f32 = 0.866_025_4;
 const SIDE: f32 = 22.0;
+const EVACUATE_HOME_AFTER_TURNS: usize = 30;
+const NO_PROGRESS_MOVES: usize = 100;
+/// how often a position may come up before it's a draw, on unless turned
+/// off on the setup screen
+const REPETITIONS: usize = 3;
+const SIDE_PANEL_WIDTH: f32 = 260.0;
+/// (seats, sides per seat) layouts offered on the setup screen besides
+/// picking single sides freely
+const SEAT_LAYOUTS: [(usize, usize); 3] = [(2, 2), (2, 3), (3, 2)];
+/// the computer players offered on the setup screen
+const COMPUTER_BOTS: [BotKind; 3] = [BotKind::Greedy, BotKind::Minimax(1), BotKind::Minimax(2)];
+/// the least time the computer takes over a move, so its moves can be
+/// followed
+const COMPUTER_MOVE_TIME: Duration = Duration::from_millis(600);
+/// how many moves ahead the hint search looks
+const HINT_DEPTH: u8 = 2;
+/// hints per game offered on the setup screen, `None` being unlimited
+const HINT_BUDGETS: [Option<usize>; 4] = [Some(0), Some(3), Some(10), None];
+/// how many moves ahead the post-game analysis looks
+const ANALYSIS_DEPTH: u8 = 2;
+/// the most blunders listed in the analysis panel
+const LISTED_BLUNDERS: usize = 12;
+const ANALYSIS_REPORT_PATH: &str = "analysis.txt";
+const ANALYSIS_JSON_PATH: &str = "analysis.json";
+/// the most profiles listed on the leaderboard
+const LEADERBOARD_ROWS: usize = 12;
+/// past games listed on a page of the history
+const HISTORY_ROWS: usize = 6;
+/// the most players whose stats the history lists
+const HISTORY_STATS_ROWS: usize = 6;
+/// moves listed on a page of the move list of the game in play
+const MOVE_LIST_ROWS: usize = 12;
+const EXPORTED_POSITION_PATH: &str = "position.txt";
+const HIGHLIGHT_COLOR: u32 = 0xFFAA00;
+/// how far the outline of the star runs from the centers of the outermost
+/// holes
+const STAR_MARGIN: f32 = 0.75 * SIDE;
+/// the holes at the inner corners of the star, between its points
+const STAR_INNER_CORNERS: [(i32, i32); 6] = [(4, -4), (4, 0), (0, 4), (-4, 4), (-4, 0), (0, -4)];
+/// how strongly the points of the star take their owner's color, for sides
+/// in the game and out of it
+const PLAYING_TINT: f32 = 0.35;
+const IDLE_TINT: f32 = 0.12;
+/// how long a move's trail takes to fade down to `TRAIL_FADED_ALPHA`, which
+/// it keeps until the player moves again
+const TRAIL_FADE_SECS: f32 = 3.0;
+const TRAIL_FADED_ALPHA: f32 = 0.35;
 
 impl SideOfStar {
     fn color(self) -> Color {
@@ -82,11 +150,39 @@ fn ideal_radius(hexagon_side: f32) -> f32 {
     hexagon_side * COS_30_DEG * 0.85
 }
 
+/// The corners of `side`'s home triangle, pushed out so its edges run
+/// `STAR_MARGIN` from the centers of its holes.
+fn home_triangle(side: SideOfStar) -> Vec<Point> {
+    let centers: Vec<Point> = side
+        .home()
+        .into_iter()
+        .map(|coord| coord.hexagon_center(SIDE))
+        .collect();
+    let centroid = centers.iter().fold(Point::origin(), |sum, center| {
+        sum + center.coords / centers.len() as f32
+    });
+    let mut corners = centers;
+    corners.sort_by(|a, b| (*b - centroid).norm().total_cmp(&(*a - centroid).norm()));
+    corners.truncate(3);
+    corners
+        .into_iter()
+        .map(|corner| corner + (corner - centroid).normalize() * 2.0 * STAR_MARGIN)
+        .collect()
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Phase {
     Start,
     Setup,
     Play,
+    Leaderboard,
+    History,
+    Replay,
+    Editor,
+    Puzzles,
+    Puzzle,
+    Tutorial,
+    Solitaire,
 }
 
 impl Phase {
@@ -95,7 +191,8 @@ impl Phase {
         match self {
             Start => Setup,
             Setup => Play,
-            Play => unreachable!(),
+            Play | Leaderboard | History | Replay | Editor | Puzzles | Puzzle | Tutorial
+            | Solitaire => unreachable!(),
         }
     }
     fn previous(self) -> Self {
@@ -103,7 +200,10 @@ impl Phase {
         match self {
             Start => unreachable!(),
             Setup => Start,
-            Play => Setup,
+            Play | Editor => Setup,
+            Leaderboard | History | Puzzles | Tutorial | Solitaire => Start,
+            Replay => History,
+            Puzzle => Puzzles,
         }
     }
 }
@@ -135,34 +235,427 @@ impl LiftedPiece {
     }
 }
 
+/// A past game being stepped through.
+#[derive(Clone, Debug)]
+struct Replay {
+    /// the index into `Archive::games`
+    game: usize,
+    moves_shown: usize,
+    board: Board,
+}
+
+/// A puzzle being worked on.
+#[derive(Clone, Debug)]
+struct PuzzleState {
+    /// the index into `BoardGame::puzzles`
+    index: usize,
+    attempt: Attempt,
+    /// what became of the last move tried
+    last_try: Option<Attempted>,
+    /// whether the answer was shown, after which solving doesn't count
+    answer_shown: bool,
+}
+
+/// Someone going through the tutorial.
+#[derive(Clone, Debug)]
+struct TutorialState {
+    lessons: Vec<Lesson>,
+    /// the index into `lessons`
+    index: usize,
+    /// the lesson's board, with the move made once it's done
+    board: Board,
+    done: bool,
+    /// whether the piece was dropped somewhere other than asked
+    missed: bool,
+}
+
+impl TutorialState {
+    fn lesson(&self) -> &Lesson {
+        &self.lessons[self.index]
+    }
+
+    /// Starts over with the lesson at `index`.
+    fn open_lesson(&mut self, index: usize) {
+        if let Some(lesson) = self.lessons.get(index) {
+            self.index = index;
+            self.board = lesson.board.clone();
+            self.done = lesson.task.is_none();
+            self.missed = false;
+        }
+    }
+
+    /// The piece the lesson asks to move, once and if there's one to move.
+    fn movable_piece(&self) -> Option<HexCoord> {
+        match self.lesson().task {
+            Some((start, _)) if !self.done => Some(start),
+            _ => None,
+        }
+    }
+}
+
+/// A side crossing the board alone.
+#[derive(Clone, Debug)]
+struct SolitaireGame {
+    challenge: Challenge,
+    board: Board,
+    /// whether the result went into the records
+    recorded: bool,
+    new_best: bool,
+}
+
+/// The move a bot or search picked, `None` if there was none to make.
+type Chosen = Option<(HexCoord, HexCoord)>;
+
+/// A move the computer is working out in the background, to play or to
+/// suggest.
+#[derive(Debug)]
+struct Thinking<T> {
+    /// how many moves had been made when it started thinking
+    moves_made: usize,
+    receiver: Receiver<T>,
+}
+
+/// A player's last move, drawn on the board in play.
 #[derive(Clone, Debug)]
+struct Trail {
+    /// the holes the piece passed through, where it started and ended
+    /// included
+    path: Vec<HexCoord>,
+    made: Instant,
+}
+
+#[derive(Debug)]
 struct BoardGame {
     inner_board: Board,
+    rules: AntiSpoiling,
+    seat_layout: Option<(usize, usize)>,
+    /// the sides the computer plays; it plays a seat if it plays any of the
+    /// seat's sides
+    computer_sides: BTreeSet<Player>,
+    computer_bot: BotKind,
+    computer_move: Option<Thinking<Chosen>>,
+    seat_kind_button_states: Vec<button::State>,
     grid_center: [f32; 2],
     phase: Phase,
     lifted_piece: Option<LiftedPiece>,
     next_button_state: button::State,
     previous_button_state: button::State,
+    goal_button_states: Vec<button::State>,
+    show_standings: bool,
+    /// whether every hole is labelled with its coordinates
+    show_coordinates: bool,
+    /// the last move of every player who moved
+    trails: BTreeMap<Player, Trail>,
+    show_trails: bool,
+    /// the game in play as it started, to look back at earlier positions
+    play_start: Board,
+    /// an earlier position of the game in play and how many moves it came
+    /// after, shown instead of the live board without letting anyone move
+    viewed_position: Option<(usize, Board)>,
+    /// how many pages back from the latest moves the move list is
+    move_list_page: usize,
+    view_move_button_states: Vec<button::State>,
+    earlier_moves_button_state: button::State,
+    later_moves_button_state: button::State,
+    back_to_live_button_state: button::State,
+    /// the player offering a draw, while the others decide
+    draw_offer: Option<Player>,
+    offer_draw_button_state: button::State,
+    accept_draw_button_state: button::State,
+    decline_draw_button_state: button::State,
+    /// the search behind the hints, kept for its transposition table and
+    /// handed to the background while it works out a hint
+    search: Option<engine::Search>,
+    hint_search: Option<Thinking<(engine::Search, Chosen)>>,
+    /// the suggested move, shown until a move is made
+    hint: Option<(HexCoord, HexCoord)>,
+    hint_budget: Option<usize>,
+    hints_left: Option<usize>,
+    hint_button_state: button::State,
+    analysis: Option<engine::Analysis>,
+    /// the analysis being worked out in the background
+    analyzing: Option<Receiver<engine::Analysis>>,
+    /// what became of the last attempt to save the analysis
+    analysis_saved: Option<String>,
+    analyze_button_state: button::State,
+    save_analysis_button_state: button::State,
+    profiles: Profiles,
+    /// what went wrong loading, saving or adding profiles
+    profiles_error: Option<String>,
+    /// the profile playing each side, guests having none
+    seat_profiles: BTreeMap<Player, String>,
+    /// the name typed in for a new profile
+    new_profile_name: String,
+    /// whether the ratings were updated for the finished game
+    game_recorded: bool,
+    leaderboard_button_state: button::State,
+    add_profile_button_state: button::State,
+    profile_button_states: Vec<button::State>,
+    profile_color_button_states: Vec<button::State>,
+    archive: Archive,
+    /// what went wrong loading or saving the archive
+    archive_error: Option<String>,
+    game_started: Instant,
+    /// only list the games this player played
+    history_player: Option<String>,
+    /// only list the games with this many players
+    history_players_count: Option<usize>,
+    history_page: usize,
+    replay: Option<Replay>,
+    history_button_state: button::State,
+    history_player_button_state: button::State,
+    history_players_count_button_state: button::State,
+    newer_games_button_state: button::State,
+    older_games_button_state: button::State,
+    open_replay_button_states: Vec<button::State>,
+    replay_button_states: [button::State; 4],
+    /// what clicking a hole puts there in the editor
+    editor_brush: Spot,
+    /// whether the game started from a position set up in the editor, which
+    /// doesn't count for the ratings and is archived with that position
+    edited_position: bool,
+    /// what became of the last export of the edited position
+    position_exported: Option<String>,
+    /// why the editor's board couldn't be put back in the start position
+    reset_error: Option<String>,
+    edit_position_button_state: button::State,
+    editor_turn_button_state: button::State,
+    clear_board_button_state: button::State,
+    reset_board_button_state: button::State,
+    start_edited_button_state: button::State,
+    export_position_button_state: button::State,
+    puzzles: Vec<Puzzle>,
+    puzzle_progress: PuzzleProgress,
+    /// what went wrong loading or saving the puzzle progress, or opening a
+    /// puzzle
+    puzzles_error: Option<String>,
+    puzzle: Option<PuzzleState>,
+    puzzles_button_state: button::State,
+    open_puzzle_button_states: Vec<button::State>,
+    show_answer_button_state: button::State,
+    retry_puzzle_button_state: button::State,
+    next_puzzle_button_state: button::State,
+    tutorial: Option<TutorialState>,
+    tutorial_button_state: button::State,
+    previous_lesson_button_state: button::State,
+    next_lesson_button_state: button::State,
+    solitaire: Option<SolitaireGame>,
+    solitaire_records: SolitaireRecords,
+    /// what went wrong loading or saving the solitaire records
+    solitaire_error: Option<String>,
+    /// the par of each challenge once worked out, the same for every side
+    solitaire_pars: BTreeMap<Obstacles, Option<SolitaireSolution>>,
+    /// the pars being worked out in the background
+    solitaire_solving: BTreeMap<Obstacles, Receiver<Option<SolitaireSolution>>>,
+    solitaire_button_state: button::State,
+    solitaire_side_button_state: button::State,
+    restart_solitaire_button_state: button::State,
+    /// the sprites and textures, drawing plain meshes without them
+    assets: Option<Assets>,
+    /// why the assets couldn't be loaded
+    assets_error: Option<String>,
+    labels: Option<Labels>,
+    sounds: Sounds,
+    /// what went wrong loading the sounds, or loading or saving the sound
+    /// settings
+    sound_error: Option<String>,
+    quieter_button_state: button::State,
+    louder_button_state: button::State,
 }
 
 impl BoardGame {
     fn new() -> Self {
+        let (profiles, profiles_error) = match Profiles::load(PROFILES_PATH) {
+            Ok(profiles) => (profiles, None),
+            Err(error) => (
+                Profiles::default(),
+                Some(format!("Couldn't load {}: {}", PROFILES_PATH, error)),
+            ),
+        };
+        let (archive, archive_error) = match Archive::load(ARCHIVE_PATH) {
+            Ok(archive) => (archive, None),
+            Err(error) => (
+                Archive::default(),
+                Some(format!("Couldn't load {}: {}", ARCHIVE_PATH, error)),
+            ),
+        };
+        let (puzzle_progress, puzzles_error) = match PuzzleProgress::load(PUZZLE_PROGRESS_PATH) {
+            Ok(progress) => (progress, None),
+            Err(error) => (
+                PuzzleProgress::default(),
+                Some(format!("Couldn't load {}: {}", PUZZLE_PROGRESS_PATH, error)),
+            ),
+        };
+        let puzzles = Puzzle::bundled();
+        let (sound_settings, sound_error) = match SoundSettings::load(SOUND_SETTINGS_PATH) {
+            Ok(settings) => (settings, None),
+            Err(error) => (
+                SoundSettings::default(),
+                Some(format!("Couldn't load {}: {}", SOUND_SETTINGS_PATH, error)),
+            ),
+        };
+        let (solitaire_records, solitaire_error) =
+            match SolitaireRecords::load(SOLITAIRE_RECORDS_PATH) {
+                Ok(records) => (records, None),
+                Err(error) => (
+                    SolitaireRecords::default(),
+                    Some(format!(
+                        "Couldn't load {}: {}",
+                        SOLITAIRE_RECORDS_PATH, error
+                    )),
+                ),
+            };
         Self {
-            inner_board: Board::new(2),
+            inner_board: {
+                let mut board = Board::new(2).expect("two players is a valid setup");
+                board.draw_rules.repetitions = Some(REPETITIONS);
+                board
+            },
+            rules: AntiSpoiling::default(),
+            seat_layout: None,
+            computer_sides: BTreeSet::new(),
+            computer_bot: BotKind::Greedy,
+            computer_move: None,
+            seat_kind_button_states: vec![button::State::default(); SideOfStar::all().len()],
             grid_center: [450.0, 350.0],
             phase: Phase::Start,
             lifted_piece: None,
             next_button_state: button::State::default(),
             previous_button_state: button::State::default(),
+            goal_button_states: vec![button::State::default(); SideOfStar::all().len()],
+            show_standings: false,
+            show_coordinates: false,
+            trails: BTreeMap::new(),
+            show_trails: true,
+            play_start: Board::new(2).expect("two players is a valid setup"),
+            viewed_position: None,
+            move_list_page: 0,
+            view_move_button_states: vec![button::State::default(); MOVE_LIST_ROWS],
+            earlier_moves_button_state: button::State::default(),
+            later_moves_button_state: button::State::default(),
+            back_to_live_button_state: button::State::default(),
+            draw_offer: None,
+            offer_draw_button_state: button::State::default(),
+            accept_draw_button_state: button::State::default(),
+            decline_draw_button_state: button::State::default(),
+            search: Some(engine::Search::new(HINT_DEPTH)),
+            hint_search: None,
+            hint: None,
+            hint_budget: Some(3),
+            hints_left: Some(3),
+            hint_button_state: button::State::default(),
+            analysis: None,
+            analyzing: None,
+            analysis_saved: None,
+            analyze_button_state: button::State::default(),
+            save_analysis_button_state: button::State::default(),
+            profiles,
+            profiles_error,
+            seat_profiles: BTreeMap::new(),
+            new_profile_name: String::new(),
+            game_recorded: false,
+            leaderboard_button_state: button::State::default(),
+            add_profile_button_state: button::State::default(),
+            profile_button_states: vec![button::State::default(); SideOfStar::all().len()],
+            profile_color_button_states: Vec::new(),
+            archive,
+            archive_error,
+            game_started: Instant::now(),
+            history_player: None,
+            history_players_count: None,
+            history_page: 0,
+            replay: None,
+            history_button_state: button::State::default(),
+            history_player_button_state: button::State::default(),
+            history_players_count_button_state: button::State::default(),
+            newer_games_button_state: button::State::default(),
+            older_games_button_state: button::State::default(),
+            open_replay_button_states: vec![button::State::default(); HISTORY_ROWS],
+            replay_button_states: Default::default(),
+            editor_brush: Spot::Empty,
+            edited_position: false,
+            position_exported: None,
+            reset_error: None,
+            edit_position_button_state: button::State::default(),
+            editor_turn_button_state: button::State::default(),
+            clear_board_button_state: button::State::default(),
+            reset_board_button_state: button::State::default(),
+            start_edited_button_state: button::State::default(),
+            export_position_button_state: button::State::default(),
+            open_puzzle_button_states: vec![button::State::default(); puzzles.len()],
+            puzzles,
+            puzzle_progress,
+            puzzles_error,
+            puzzle: None,
+            puzzles_button_state: button::State::default(),
+            show_answer_button_state: button::State::default(),
+            retry_puzzle_button_state: button::State::default(),
+            next_puzzle_button_state: button::State::default(),
+            tutorial: None,
+            tutorial_button_state: button::State::default(),
+            previous_lesson_button_state: button::State::default(),
+            next_lesson_button_state: button::State::default(),
+            solitaire: None,
+            solitaire_records,
+            solitaire_error,
+            solitaire_pars: BTreeMap::new(),
+            solitaire_solving: BTreeMap::new(),
+            solitaire_button_state: button::State::default(),
+            solitaire_side_button_state: button::State::default(),
+            restart_solitaire_button_state: button::State::default(),
+            assets: None,
+            assets_error: None,
+            labels: None,
+            sounds: Sounds::new(sound_settings, Box::new(Silent)),
+            sound_error,
+            quieter_button_state: button::State::default(),
+            louder_button_state: button::State::default(),
+        }
+    }
+
+    /// Gets ready to play the game set up on `inner_board`.
+    fn start_play(&mut self, edited_position: bool) {
+        self.draw_offer = None;
+        self.hint = None;
+        self.hints_left = self.hint_budget;
+        self.trails.clear();
+        self.computer_move = None;
+        let computer_sides = &self.computer_sides;
+        for seat in &mut self.inner_board.seats {
+            seat.kind = if seat.sides.iter().any(|side| computer_sides.contains(side)) {
+                SeatKind::Computer
+            } else {
+                SeatKind::Human
+            };
         }
+        self.play_start = self.inner_board.clone();
+        self.viewed_position = None;
+        self.move_list_page = 0;
+        self.analysis = None;
+        self.analyzing = None;
+        self.game_recorded = false;
+        self.edited_position = edited_position;
+        self.game_started = Instant::now();
+        self.search = Some(engine::Search::new(HINT_DEPTH));
+        self.hint_search = None;
+        self.phase = Phase::Play;
     }
 
-    fn circle_mesh(&self) -> Mesh {
-        let circle_centers = self
-            .inner_board
-            .board
+    fn export_position(&mut self) {
+        let exported = std::fs::write(
+            EXPORTED_POSITION_PATH,
+            self.inner_board.to_position_string() + "\n",
+        );
+        self.position_exported = Some(match exported {
+            Ok(()) => format!("Exported to {}", EXPORTED_POSITION_PATH),
+            Err(error) => format!("Couldn't export: {}", error),
+        });
+    }
+
+    fn circle_mesh(board: &Board) -> Mesh {
+        let circle_centers = board
             .iter()
-            .map(|(&coord, &spot)| (coord.hexagon_center(SIDE), spot.color()));
+            .map(|(coord, spot)| (coord.hexagon_center(SIDE), spot.color()));
 
         let mut mesh = Mesh::new_with_tolerance(0.05);
 
@@ -178,30 +671,714 @@ impl BoardGame {
 
         mesh
     }
+
+    /// The star under the holes, each point tinted in the color of the side
+    /// whose home it is and the goal of the side to move outlined in its
+    /// color.
+    fn star_mesh(board: &Board) -> Mesh {
+        let mut mesh = Mesh::new_with_tolerance(0.05);
+
+        let tips = SideOfStar::all().into_iter().map(|side| {
+            let tip = side
+                .home()
+                .into_iter()
+                .map(|coord| coord.hexagon_center(SIDE).coords)
+                .max_by(|a, b| a.norm().total_cmp(&b.norm()))
+                .expect("every home has holes");
+            tip + tip.normalize() * 2.0 * STAR_MARGIN
+        });
+        let inner_corners = STAR_INNER_CORNERS.iter().map(|&(horz, slant)| {
+            let corner = HexCoord::new(horz, slant).hexagon_center(SIDE).coords;
+            corner + corner.normalize() * 2.0 * STAR_MARGIN / 3.0_f32.sqrt()
+        });
+        let mut outline: Vec<_> = tips.chain(inner_corners).collect();
+        outline.sort_by(|a, b| a.y.atan2(a.x).total_cmp(&b.y.atan2(b.x)));
+        let mut outline: Vec<Point> = outline.into_iter().map(Point::from).collect();
+        mesh.fill(
+            Shape::Polyline {
+                points: outline.clone(),
+            },
+            Color::new(1.0, 1.0, 1.0, 0.1),
+        );
+
+        for side in SideOfStar::all() {
+            let tint = if board.players.contains(&side) {
+                PLAYING_TINT
+            } else {
+                IDLE_TINT
+            };
+            let color = side.color();
+            mesh.fill(
+                Shape::Polyline {
+                    points: home_triangle(side),
+                },
+                Color::new(color.r, color.g, color.b, tint),
+            );
+        }
+        if !board.players.is_empty() {
+            let mut goal = home_triangle(board.goal_side(board.turn));
+            goal.push(goal[0]);
+            mesh.stroke(Shape::Polyline { points: goal }, board.turn.color(), 4.0);
+        }
+
+        outline.push(outline[0]);
+        mesh.stroke(
+            Shape::Polyline { points: outline },
+            Spot::Empty.color(),
+            2.0,
+        );
+        mesh
+    }
+
+    /// Every player's last move: a ring where the piece started, the hops
+    /// it made and a thicker ring where it ended, fading as it gets older.
+    fn trail_mesh(trails: &BTreeMap<Player, Trail>) -> Mesh {
+        let mut by_age: Vec<_> = trails.iter().collect();
+        by_age.sort_by_key(|(_, trail)| trail.made);
+
+        let mut mesh = Mesh::new_with_tolerance(0.05);
+        for (player, trail) in by_age {
+            let (start, end) = match (trail.path.first(), trail.path.last()) {
+                (Some(&start), Some(&end)) => (start, end),
+                _ => continue,
+            };
+            let faded = trail.made.elapsed().as_secs_f32() / TRAIL_FADE_SECS;
+            let alpha = 1.0 - (1.0 - TRAIL_FADED_ALPHA) * faded.min(1.0);
+            let color = player.color();
+            let color = Color::new(color.r, color.g, color.b, alpha);
+            let ring = |coord: HexCoord| Shape::Circle {
+                center: coord.hexagon_center(SIDE),
+                radius: ideal_radius(SIDE) + 2.0,
+            };
+
+            mesh.stroke(ring(start), color, 2.0);
+            mesh.stroke(
+                Shape::Polyline {
+                    points: trail
+                        .path
+                        .iter()
+                        .map(|coord| coord.hexagon_center(SIDE))
+                        .collect(),
+                },
+                color,
+                3.0,
+            );
+            mesh.stroke(ring(end), color, 5.0);
+        }
+        mesh
+    }
+
+    /// Rings around the holes at `coords`.
+    fn highlight_mesh(coords: &[HexCoord]) -> Mesh {
+        let mut mesh = Mesh::new_with_tolerance(0.05);
+        for coord in coords {
+            mesh.stroke(
+                Shape::Circle {
+                    center: coord.hexagon_center(SIDE),
+                    radius: ideal_radius(SIDE) + 2.0,
+                },
+                Color::from_rgb_u32(HIGHLIGHT_COLOR),
+                3.0,
+            );
+        }
+        mesh
+    }
+
+    /// The path of the suggested move, ending in an arrowhead.
+    fn hint_mesh(board: &Board, (start, end): (HexCoord, HexCoord)) -> Option<Mesh> {
+        let points: Vec<Point> = board
+            .move_path(start, end)
+            .into_iter()
+            .map(|coord| coord.hexagon_center(SIDE))
+            .collect();
+        let (&tip, &before_tip) = (points.last()?, points.get(points.len().checked_sub(2)?)?);
+
+        let direction = (tip - before_tip).normalize();
+        let across = Point::new(-direction.y, direction.x).coords;
+        let head_length = SIDE * 0.9;
+        let base = tip - direction * head_length;
+        let arrowhead = vec![
+            tip,
+            base + across * head_length * 0.5,
+            base - across * head_length * 0.5,
+            tip,
+        ];
+        let color = board.turn.color();
+
+        let mut mesh = Mesh::new();
+        let mut shaft = points;
+        *shaft.last_mut()? = base;
+        mesh.stroke(
+            Shape::Polyline {
+                points: shaft.clone(),
+            },
+            Color::BLACK,
+            7.0,
+        );
+        mesh.stroke(Shape::Polyline { points: shaft }, color, 4.0);
+        mesh.stroke(
+            Shape::Polyline {
+                points: arrowhead.clone(),
+            },
+            Color::BLACK,
+            3.0,
+        );
+        mesh.fill(Shape::Polyline { points: arrowhead }, color);
+        Some(mesh)
+    }
+
+    /// Sets the search working out a hint in the background.
+    fn show_hint(&mut self) {
+        if self.hints_left == Some(0) || self.inner_board.is_over() || self.hint_search.is_some() {
+            return;
+        }
+        let mut search = self
+            .search
+            .take()
+            .unwrap_or_else(|| engine::Search::new(HINT_DEPTH));
+        let (sender, receiver) = mpsc::channel();
+        let board = self.inner_board.clone();
+        thread::spawn(move || {
+            let hint = search.best_move(&board);
+            let _ = sender.send((search, hint));
+        });
+        self.hint_search = Some(Thinking {
+            moves_made: self.inner_board.history.len(),
+            receiver,
+        });
+        if let Some(hints_left) = self.hints_left.as_mut() {
+            *hints_left -= 1;
+        }
+    }
+
+    /// Shows the hint once it's worked out, unless a move was made since.
+    fn receive_hint(&mut self) {
+        let received = match &self.hint_search {
+            Some(hint_search) => hint_search.receiver.try_recv(),
+            None => return,
+        };
+        match received {
+            Ok((search, hint)) => {
+                let moves_made = self
+                    .hint_search
+                    .as_ref()
+                    .map(|thinking| thinking.moves_made);
+                if moves_made == Some(self.inner_board.history.len()) {
+                    self.hint = hint;
+                }
+                self.search = Some(search);
+                self.hint_search = None;
+            }
+            Err(TryRecvError::Empty) => {}
+            Err(TryRecvError::Disconnected) => self.hint_search = None,
+        }
+    }
+
+    fn save_profiles(&mut self) {
+        self.profiles_error = self
+            .profiles
+            .save(PROFILES_PATH)
+            .err()
+            .map(|error| format!("Couldn't save {}: {}", PROFILES_PATH, error));
+    }
+
+    fn add_profile(&mut self) {
+        match self.profiles.add(&self.new_profile_name) {
+            Ok(_) => {
+                self.new_profile_name.clear();
+                self.save_profiles();
+            }
+            Err(error) => self.profiles_error = Some(error.to_string()),
+        }
+    }
+
+    fn type_profile_name(&mut self, typed: &str) {
+        for character in typed.chars() {
+            match character {
+                '\u{8}' => {
+                    self.new_profile_name.pop();
+                }
+                '\r' | '\n' => self.add_profile(),
+                character
+                    if !character.is_control()
+                        && self.new_profile_name.chars().count() < MAX_NAME_LENGTH =>
+                {
+                    self.new_profile_name.push(character);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Archives the game once it ends, and updates the ratings if at least
+    /// two profiles played.
+    fn record_game(&mut self) {
+        if self.game_recorded || !self.inner_board.is_over() {
+            return;
+        }
+        self.game_recorded = true;
+        let board = &self.inner_board;
+        let archived = ArchivedGame::new(
+            board,
+            Some(&self.play_start).filter(|_| self.edited_position),
+            self.rules,
+            &self.seat_profiles,
+            self.game_started.elapsed(),
+        );
+        // a game from an edited position might have started as good as won
+        if !self.edited_position
+            && self
+                .profiles
+                .record_game(&self.seat_profiles, &board.players, &board.outcome)
+        {
+            self.save_profiles();
+        }
+
+        self.archive.games.push(archived);
+        self.archive_error = self
+            .archive
+            .save(ARCHIVE_PATH)
+            .err()
+            .map(|error| format!("Couldn't save {}: {}", ARCHIVE_PATH, error));
+    }
+
+    /// Plays a move in the game in play, if it's legal.
+    fn play_move(&mut self, start: HexCoord, end: HexCoord) {
+        let moves_made = self.inner_board.history.len();
+        let player = self.inner_board.turn;
+        let path = self.inner_board.move_path(start, end);
+        self.inner_board.make_move(start, end);
+        if self.inner_board.history.len() == moves_made {
+            if start != end {
+                self.sounds.play(SoundEvent::IllegalDrop);
+            }
+        } else {
+            self.sounds
+                .play_move(&self.inner_board, player, path.len().saturating_sub(1));
+            self.hint = None;
+            let made = Instant::now();
+            self.trails.insert(player, Trail { path, made });
+        }
+    }
+
+    /// Plays the computer's move once it has worked it out, or sets it
+    /// thinking in the background when a seat it plays is to move.
+    fn play_computer_move(&mut self) {
+        let board = &self.inner_board;
+        if let Some(computer_move) = &self.computer_move {
+            match computer_move.receiver.try_recv() {
+                Ok(chosen) => {
+                    let still_current = computer_move.moves_made == board.history.len();
+                    self.computer_move = None;
+                    if let (true, Some((start, end))) = (still_current, chosen) {
+                        self.play_move(start, end);
+                    }
+                }
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => self.computer_move = None,
+            }
+            return;
+        }
+
+        let is_computer = board
+            .seat_of(board.turn)
+            .is_some_and(|seat| seat.kind == SeatKind::Computer);
+        if !is_computer || board.is_over() || self.draw_offer.is_some() {
+            return;
+        }
+        let (sender, receiver) = mpsc::channel();
+        let board = board.clone();
+        let bot = self.computer_bot;
+        let moves_made = board.history.len();
+        thread::spawn(move || {
+            let started = Instant::now();
+            let chosen = bot.build(moves_made as u64).choose_move(&board);
+            if let Some(left) = COMPUTER_MOVE_TIME.checked_sub(started.elapsed()) {
+                thread::sleep(left);
+            }
+            let _ = sender.send(chosen);
+        });
+        self.computer_move = Some(Thinking {
+            moves_made,
+            receiver,
+        });
+    }
+
+    /// Shows the game in play as it was after `moves_shown` moves, or the
+    /// live board once that's all of them.
+    fn view_position(&mut self, moves_shown: usize) {
+        let history = &self.inner_board.history;
+        if moves_shown >= history.len() {
+            self.viewed_position = None;
+            return;
+        }
+        let mut board = self.play_start.clone();
+        for played in &history[..moves_shown] {
+            board.make_move(played.start, played.end);
+        }
+        self.lifted_piece = None;
+        self.viewed_position = Some((moves_shown, board));
+    }
+
+    fn show_replay(&mut self, game: usize, moves_shown: usize) {
+        let archived = match self.archive.games.get(game) {
+            Some(archived) => archived,
+            None => return,
+        };
+        let moves_shown = moves_shown.min(archived.moves.len());
+        match archived.board_after(moves_shown) {
+            Ok(board) => {
+                self.replay = Some(Replay {
+                    game,
+                    moves_shown,
+                    board,
+                });
+                self.phase = Phase::Replay;
+            }
+            Err(error) => {
+                self.archive_error = Some(format!("Can't replay that game: {}", error));
+            }
+        }
+    }
+
+    fn save_sound_settings(&mut self) {
+        self.sound_error = self
+            .sounds
+            .settings
+            .save(SOUND_SETTINGS_PATH)
+            .err()
+            .map(|error| format!("Couldn't save {}: {}", SOUND_SETTINGS_PATH, error));
+    }
+
+    fn save_puzzle_progress(&mut self) {
+        self.puzzles_error = self
+            .puzzle_progress
+            .save(PUZZLE_PROGRESS_PATH)
+            .err()
+            .map(|error| format!("Couldn't save {}: {}", PUZZLE_PROGRESS_PATH, error));
+    }
+
+    fn open_puzzle(&mut self, index: usize) {
+        let puzzle = match self.puzzles.get(index) {
+            Some(puzzle) => puzzle,
+            None => return,
+        };
+        match Attempt::new(puzzle) {
+            Ok(attempt) => {
+                self.puzzle_progress.start(puzzle);
+                self.puzzle = Some(PuzzleState {
+                    index,
+                    attempt,
+                    last_try: None,
+                    answer_shown: false,
+                });
+                self.hint = None;
+                self.lifted_piece = None;
+                self.phase = Phase::Puzzle;
+                self.save_puzzle_progress();
+            }
+            Err(error) => {
+                self.puzzles_error = Some(format!("Can't open {}: {}", puzzle.name, error));
+            }
+        }
+    }
+
+    /// Tries the dropped piece's move on the puzzle, which only plays it if
+    /// it's an answer.
+    fn try_puzzle_move(&mut self) {
+        let (start, end) = match &self.lifted_piece {
+            Some(LiftedPiece {
+                piece_coord,
+                drop_coord: Some(drop_coord),
+                ..
+            }) => (*piece_coord, *drop_coord),
+            _ => return,
+        };
+        self.lifted_piece = None;
+        let puzzle = match self.puzzle.as_mut() {
+            Some(puzzle) => puzzle,
+            None => return,
+        };
+
+        let side = puzzle.attempt.board.turn;
+        let hops = puzzle.attempt.board.move_path(start, end).len();
+        let result = puzzle.attempt.try_move(start, end);
+        match result {
+            Attempted::Illegal if start != end => self.sounds.play(SoundEvent::IllegalDrop),
+            Attempted::Illegal => {}
+            Attempted::Wrong => self.sounds.play(SoundEvent::IllegalDrop),
+            Attempted::Correct => {
+                self.sounds
+                    .play_move(&puzzle.attempt.board, side, hops.saturating_sub(1))
+            }
+            Attempted::Solved => {
+                self.sounds
+                    .play_move(&puzzle.attempt.board, side, hops.saturating_sub(1));
+                self.sounds.play(SoundEvent::GameOver);
+            }
+        }
+        if result == Attempted::Illegal {
+            return;
+        }
+        puzzle.last_try = Some(result);
+        self.hint = None;
+        if result == Attempted::Solved && !puzzle.answer_shown {
+            self.puzzle_progress.solve(&self.puzzles[puzzle.index]);
+            self.save_puzzle_progress();
+        }
+    }
+
+    /// Makes the dropped piece's move if it's the one the lesson asks for.
+    fn try_lesson_move(&mut self) {
+        let (start, end) = match &self.lifted_piece {
+            Some(LiftedPiece {
+                piece_coord,
+                drop_coord: Some(drop_coord),
+                ..
+            }) => (*piece_coord, *drop_coord),
+            _ => return,
+        };
+        self.lifted_piece = None;
+        let tutorial = match self.tutorial.as_mut() {
+            Some(tutorial) => tutorial,
+            None => return,
+        };
+
+        if tutorial.lesson().accepts(start, end) {
+            let side = tutorial.board.turn;
+            let hops = tutorial.board.move_path(start, end).len();
+            tutorial.board.make_move(start, end);
+            self.sounds
+                .play_move(&tutorial.board, side, hops.saturating_sub(1));
+            tutorial.done = true;
+            tutorial.missed = false;
+        } else if start != end {
+            self.sounds.play(SoundEvent::IllegalDrop);
+            tutorial.missed = true;
+        }
+    }
+
+    fn start_solitaire(&mut self, challenge: Challenge) {
+        let obstacles = challenge.obstacles;
+        if !self.solitaire_pars.contains_key(&obstacles)
+            && !self.solitaire_solving.contains_key(&obstacles)
+        {
+            let (sender, receiver) = mpsc::channel();
+            thread::spawn(move || {
+                let _ = sender.send(challenge.par());
+            });
+            self.solitaire_solving.insert(obstacles, receiver);
+        }
+        self.solitaire = Some(SolitaireGame {
+            challenge,
+            board: challenge.board(),
+            recorded: false,
+            new_best: false,
+        });
+        self.lifted_piece = None;
+        self.hint = None;
+        self.phase = Phase::Solitaire;
+    }
+
+    /// Makes the dropped piece's move, and keeps the result once across.
+    fn play_solitaire_move(&mut self) {
+        let (start, end) = match &self.lifted_piece {
+            Some(LiftedPiece {
+                piece_coord,
+                drop_coord: Some(drop_coord),
+                ..
+            }) => (*piece_coord, *drop_coord),
+            _ => return,
+        };
+        self.lifted_piece = None;
+        let game = match self.solitaire.as_mut() {
+            Some(game) => game,
+            None => return,
+        };
+
+        let moves_made = game.board.history.len();
+        let side = game.board.turn;
+        let hops = game.board.move_path(start, end).len();
+        game.board.make_move(start, end);
+        if game.board.history.len() != moves_made {
+            self.sounds
+                .play_move(&game.board, side, hops.saturating_sub(1));
+        } else if start != end {
+            self.sounds.play(SoundEvent::IllegalDrop);
+        }
+        if game.recorded || !game.board.is_over() {
+            return;
+        }
+        game.recorded = true;
+        game.new_best = self
+            .solitaire_records
+            .record(game.challenge, game.board.history.len());
+        if game.new_best {
+            self.solitaire_error = self
+                .solitaire_records
+                .save(SOLITAIRE_RECORDS_PATH)
+                .err()
+                .map(|error| format!("Couldn't save {}: {}", SOLITAIRE_RECORDS_PATH, error));
+        }
+    }
+
+    /// Keeps the pars worked out since the last frame.
+    fn receive_solitaire_pars(&mut self) {
+        let mut received = Vec::new();
+        for (&obstacles, receiver) in &self.solitaire_solving {
+            match receiver.try_recv() {
+                Ok(par) => received.push((obstacles, Some(par))),
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => received.push((obstacles, None)),
+            }
+        }
+        for (obstacles, par) in received {
+            self.solitaire_solving.remove(&obstacles);
+            if let Some(par) = par {
+                self.solitaire_pars.insert(obstacles, par);
+            }
+        }
+    }
+
+    /// Analyzes the game in play in the background, as it takes a while for
+    /// long games.
+    fn analyze(&mut self) {
+        if self.analyzing.is_some() {
+            return;
+        }
+        let (sender, receiver) = mpsc::channel();
+        let board = self.inner_board.clone();
+        thread::spawn(move || {
+            let _ = sender.send(engine::analyze(&board, ANALYSIS_DEPTH));
+        });
+        self.analyzing = Some(receiver);
+        self.analysis = None;
+        self.analysis_saved = None;
+        // the analysis takes the place of the move list
+        self.viewed_position = None;
+    }
+
+    /// Shows the analysis once it's worked out.
+    fn receive_analysis(&mut self) {
+        let received = match &self.analyzing {
+            Some(receiver) => receiver.try_recv(),
+            None => return,
+        };
+        match received {
+            Ok(analysis) => {
+                self.analysis = Some(analysis);
+                self.analyzing = None;
+            }
+            Err(TryRecvError::Empty) => {}
+            Err(TryRecvError::Disconnected) => self.analyzing = None,
+        }
+    }
+
+    fn save_analysis(&mut self) {
+        let analysis = match &self.analysis {
+            Some(analysis) => analysis,
+            None => return,
+        };
+        let saved = std::fs::write(ANALYSIS_REPORT_PATH, analysis.to_string())
+            .and_then(|()| std::fs::write(ANALYSIS_JSON_PATH, analysis.to_json()));
+        self.analysis_saved = Some(match saved {
+            Ok(()) => format!(
+                "Saved to {} and {}",
+                ANALYSIS_REPORT_PATH, ANALYSIS_JSON_PATH
+            ),
+            Err(error) => format!("Couldn't save: {}", error),
+        });
+    }
 }
 
 impl Game for BoardGame {
     type Input = DragNDrop;
-    type LoadingScreen = ();
+    type LoadingScreen = Splash;
 
     fn load(_window: &Window) -> Task<Self> {
-        Task::succeed(|| Self::new())
+        (
+            Assets::load(),
+            Task::stage("Loading the font", Labels::load()),
+            Task::stage("Loading the sounds", load_sounds()),
+            Task::stage("Loading saved games and profiles", Task::succeed(Self::new)),
+        )
+            .join()
+            .map(|(assets, labels, sounds, mut game)| {
+                match assets {
+                    Ok(assets) => game.assets = Some(assets),
+                    Err(error) => game.assets_error = Some(error),
+                }
+                game.labels = Some(labels);
+                match sounds {
+                    Ok(backend) => game.sounds.set_backend(backend),
+                    Err(error) => game.sound_error = Some(error),
+                }
+                game
+            })
     }
 
     fn draw(&mut self, frame: &mut Frame, _timer: &Timer) {
         frame.clear(Color::BLACK);
 
-        if self.phase != Phase::Play {
-            return;
-        }
+        let board = match self.phase {
+            Phase::Play => match &self.viewed_position {
+                Some((_, board)) => board,
+                None => &self.inner_board,
+            },
+            Phase::Editor => &self.inner_board,
+            Phase::Replay => match &self.replay {
+                Some(replay) => &replay.board,
+                None => return,
+            },
+            Phase::Puzzle => match &self.puzzle {
+                Some(puzzle) => &puzzle.attempt.board,
+                None => return,
+            },
+            Phase::Tutorial => match &self.tutorial {
+                Some(tutorial) => &tutorial.board,
+                None => return,
+            },
+            Phase::Solitaire => match &self.solitaire {
+                Some(game) => &game.board,
+                None => return,
+            },
+            _ => return,
+        };
 
         let mut target = frame.as_target();
 
         let transformation = Transformation::translate(self.grid_center.into());
         let mut grid_target = target.transform(transformation);
 
-        let circles = self.circle_mesh();
-        circles.draw(&mut grid_target);
+        if let Some(assets) = &self.assets {
+            assets.draw_wood(board, &mut grid_target);
+        }
+        Self::star_mesh(board).draw(&mut grid_target);
+        match &self.assets {
+            Some(assets) => assets.draw_holes(board, &mut grid_target),
+            None => Self::circle_mesh(board).draw(&mut grid_target),
+        }
+        if let (true, Some(labels)) = (self.show_coordinates, &mut self.labels) {
+            labels.draw(board, &mut grid_target);
+        }
+        if let (Phase::Tutorial, Some(tutorial)) = (self.phase, &self.tutorial) {
+            Self::highlight_mesh(&tutorial.lesson().highlights).draw(&mut grid_target);
+        }
+        // earlier positions of the game in play are only there to look at
+        if !matches!(
+            self.phase,
+            Phase::Play | Phase::Puzzle | Phase::Tutorial | Phase::Solitaire
+        ) || self.viewed_position.is_some()
+        {
+            return;
+        }
+
+        if self.phase == Phase::Play && self.show_trails {
+            Self::trail_mesh(&self.trails).draw(&mut grid_target);
+        }
+        if let Some(hint_mesh) = self.hint.and_then(|hint| Self::hint_mesh(board, hint)) {
+            hint_mesh.draw(&mut grid_target);
+        }
 
         if let Some(lifted_piece) = &self.lifted_piece {
             let circle = |center| Shape::Circle {
@@ -212,20 +1389,14 @@ impl Game for BoardGame {
             let change_alpha = |color: Color| Color::new(color.r, color.g, color.b, DRAG_ALPHA);
 
             let mut dragndrop_mesh = Mesh::new();
-            let spot = self
-                .inner_board
-                .get(&lifted_piece.piece_coord)
-                .unwrap()
-                .clone();
+            let spot = board.get(&lifted_piece.piece_coord).copied().unwrap();
 
             let lifted_indicator = circle(lifted_piece.piece_coord.hexagon_center(SIDE));
             let lifted_indicator_color = match spot {
-                Spot::Player(player) => {
-                    match player {
-                        Player::A | Player::B | Player::C => Color::BLUE,
-                        Player::D | Player::E | Player::F => Color::RED,
-                    }
-                }
+                Spot::Player(player) => match player {
+                    Player::A | Player::B | Player::C => Color::BLUE,
+                    Player::D | Player::E | Player::F => Color::RED,
+                },
                 _ => unreachable!(),
             };
             dragndrop_mesh.fill(lifted_indicator.clone(), Spot::Empty.color());
@@ -247,15 +1418,41 @@ impl Game for BoardGame {
             window.height() * height_offset_percentage,
         ];
 
-        if self.phase != Phase::Play {
-            return;
-        }
-
         let grid_center = self.grid_center;
         let make_point_relative =
             |point: Point| -> Point { point - Point::from(grid_center).coords };
         let point_to_coord = |point: Point| HexCoord::from_point(point, SIDE);
 
+        if self.phase == Phase::Leaderboard {
+            self.type_profile_name(input.typed());
+        }
+        if self.phase == Phase::Editor {
+            if let Some(clicked) = input.clicked() {
+                let coord = point_to_coord(make_point_relative(clicked));
+                if self.inner_board.get(&coord).is_some() {
+                    match self.editor_brush {
+                        Spot::Empty => self.inner_board.remove_player(coord),
+                        Spot::Player(player) => self.inner_board.put_player(coord, player),
+                    }
+                }
+            }
+        }
+        // the tutorial only lets the piece it's about be moved
+        let (board, only_piece) = match (self.phase, &self.puzzle, &self.tutorial) {
+            (Phase::Play, ..) if self.viewed_position.is_some() => return,
+            (Phase::Play, ..) => (&self.inner_board, None),
+            (Phase::Puzzle, Some(puzzle), _) => (&puzzle.attempt.board, None),
+            (Phase::Tutorial, _, Some(tutorial)) => match tutorial.movable_piece() {
+                Some(piece) => (&tutorial.board, Some(piece)),
+                None => return,
+            },
+            (Phase::Solitaire, ..) => match &self.solitaire {
+                Some(game) => (&game.board, None),
+                None => return,
+            },
+            _ => return,
+        };
+
         if let Some((current_drag_pos, start_drag_pos)) = input.drag_status() {
             let current_drag_pos = make_point_relative(current_drag_pos);
             if let Some(lifted_piece) = self.lifted_piece.as_mut() {
@@ -265,10 +1462,17 @@ impl Game for BoardGame {
                 }
             } else {
                 let start_coord = point_to_coord(make_point_relative(start_drag_pos));
-                let spot = self.inner_board.get(&start_coord);
-                if let Some(spot) = spot {
-                    if spot == &self.inner_board.turn {
+                let spot = board.get(&start_coord);
+                let is_human = board
+                    .seat_of(board.turn)
+                    .is_none_or(|seat| seat.kind == SeatKind::Human);
+                if let Some(Spot::Player(player)) = spot {
+                    if is_human
+                        && board.movable_players().contains(player)
+                        && only_piece.is_none_or(|piece| piece == start_coord)
+                    {
                         self.lifted_piece = Some(LiftedPiece::new(start_coord, current_drag_pos));
+                        self.sounds.play(SoundEvent::Pickup);
                     }
                 }
             }
@@ -276,17 +1480,30 @@ impl Game for BoardGame {
     }
 
     fn update(&mut self, _window: &Window) {
+        self.receive_analysis();
+        self.receive_solitaire_pars();
+        match self.phase {
+            Phase::Puzzle => self.try_puzzle_move(),
+            Phase::Tutorial => self.try_lesson_move(),
+            Phase::Solitaire => self.play_solitaire_move(),
+            _ => {}
+        }
         if self.phase != Phase::Play {
             return;
         }
+        self.record_game();
 
-        if let Some(lifted_piece) = &self.lifted_piece {
-            if let Some(drop_coord) = lifted_piece.drop_coord {
-                self.inner_board
-                    .make_move(lifted_piece.piece_coord, drop_coord);
-                self.lifted_piece = None;
-            }
+        let dropped = self.lifted_piece.as_ref().and_then(|lifted_piece| {
+            lifted_piece
+                .drop_coord
+                .map(|drop_coord| (lifted_piece.piece_coord, drop_coord))
+        });
+        if let Some((start, end)) = dropped {
+            self.play_move(start, end);
+            self.lifted_piece = None;
         }
+        self.receive_hint();
+        self.play_computer_move();
     }
 
     fn cursor_icon(&self) -> CursorIcon {
@@ -303,13 +1520,73 @@ enum Message {
     Next,
     Previous,
     PlayerToggle(SideOfStar, bool),
+    CycleGoal(SideOfStar),
+    BlockedGoalToggle(bool),
+    EvacuateHomeToggle(bool),
+    TeamPlayToggle(bool),
+    StandingsToggle(bool),
+    CoordinatesToggle(bool),
+    TrailsToggle(bool),
+    MuteToggle(bool),
+    Quieter,
+    Louder,
+    /// look back at the game in play after this many moves
+    ViewPosition(usize),
+    EarlierMoves,
+    LaterMoves,
+    BackToLive,
+    RepetitionDrawToggle(bool),
+    NoProgressDrawToggle(bool),
+    OfferDraw,
+    AcceptDraw,
+    DeclineDraw,
+    Hint,
+    HintBudget(Option<usize>),
+    Analyze,
+    SaveAnalysis,
+    ShowLeaderboard,
+    AddProfile,
+    CycleProfile(SideOfStar),
+    CycleProfileColor(usize),
+    ShowHistory,
+    CycleHistoryPlayer,
+    CycleHistoryPlayersCount,
+    NewerGames,
+    OlderGames,
+    /// replay the archived game with this index from the start
+    OpenReplay(usize),
+    /// show the replayed game after this many moves
+    ReplayTo(usize),
+    EditPosition,
+    EditorBrush(Spot),
+    CycleEditorTurn,
+    ClearBoard,
+    ResetBoard,
+    StartEditedGame,
+    ExportPosition,
+    ShowPuzzles,
+    /// start the puzzle with this index from scratch
+    OpenPuzzle(usize),
+    ShowAnswer,
+    ShowTutorial,
+    /// go to the lesson with this index
+    OpenLesson(usize),
+    ShowSolitaire,
+    SolitaireObstacles(Obstacles),
+    CycleSolitaireSide,
+    RestartSolitaire,
+    SeatLayout(Option<(usize, usize)>),
+    TurnOrder(TurnOrder),
+    /// switch the seat of this side between a human and the computer
+    CycleSeatKind(SideOfStar),
+    ComputerBot(BotKind),
 }
 
 impl UserInterface for BoardGame {
     type Renderer = Renderer;
     type Message = Message;
 
-    fn layout(&mut self, window: &Window) -> Element<Self::Message> {
+    fn layout(&mut self, window: &Window) -> Element<'_, Self::Message> {
         let heading = Text::new("Chinese Checkers")
             .horizontal_alignment(HorizontalAlignment::Center)
             .size(80);
@@ -318,14 +1595,21 @@ impl UserInterface for BoardGame {
             .align_items(Align::Center)
             .spacing(20)
             .width(window.width() as u32);
-        let next_button = Button::new(&mut self.next_button_state, "Next")
-            .width(350)
-            .on_press(Message::Next);
+        let center_width = (window.width() - 2.0 * SIDE_PANEL_WIDTH).max(0.0) as u32;
+        let setup_error = match self.phase {
+            Phase::Setup => self.inner_board.validate_setup().err(),
+            _ => None,
+        };
+        let next_button = Button::new(&mut self.next_button_state, "Next").width(350);
+        let next_button = match setup_error {
+            Some(_) => next_button.class(button::Class::Secondary),
+            None => next_button.on_press(Message::Next),
+        };
         let previous_button = Button::new(&mut self.previous_button_state, "Previous")
             .width(350)
             .on_press(Message::Previous);
 
-        column = match self.phase {
+        match self.phase {
             Phase::Start => {
                 let description_text = "\
                 This is a game of Chinese Checkers. \
@@ -334,62 +1618,1174 @@ impl UserInterface for BoardGame {
                 ";
                 let description = Text::new(description_text).width(500);
 
-                column.push(heading).push(description).push(next_button)
+                let leaderboard_button =
+                    Button::new(&mut self.leaderboard_button_state, "Leaderboard")
+                        .width(350)
+                        .class(button::Class::Secondary)
+                        .on_press(Message::ShowLeaderboard);
+
+                column = column.push(heading).push(description);
+                if let Some(assets_error) = &self.assets_error {
+                    let assets_error = format!("Drawing plain pieces: {}", assets_error);
+                    column = column.push(Text::new(&assets_error).color(Color::RED).size(20));
+                }
+                column = column
+                    .push(
+                        Button::new(&mut self.tutorial_button_state, "How to play")
+                            .width(350)
+                            .class(button::Class::Secondary)
+                            .on_press(Message::ShowTutorial),
+                    )
+                    .push(next_button)
+                    .push(leaderboard_button)
+                    .push(
+                        Button::new(&mut self.history_button_state, "Past games")
+                            .width(350)
+                            .class(button::Class::Secondary)
+                            .on_press(Message::ShowHistory),
+                    )
+                    .push(
+                        Button::new(&mut self.puzzles_button_state, "Puzzles")
+                            .width(350)
+                            .class(button::Class::Secondary)
+                            .on_press(Message::ShowPuzzles),
+                    )
+                    .push(
+                        Button::new(&mut self.solitaire_button_state, "Solitaire")
+                            .width(350)
+                            .class(button::Class::Secondary)
+                            .on_press(Message::ShowSolitaire),
+                    );
+                let settings = self.sounds.settings;
+                column = column.push(
+                    Row::new()
+                        .align_items(Align::Center)
+                        .spacing(10)
+                        .push(Checkbox::new(settings.muted, "Mute", Message::MuteToggle))
+                        .push(
+                            Button::new(&mut self.quieter_button_state, "-")
+                                .class(button::Class::Secondary)
+                                .on_press(Message::Quieter),
+                        )
+                        .push(Text::new(&format!("Volume: {}%", settings.volume)).size(20))
+                        .push(
+                            Button::new(&mut self.louder_button_state, "+")
+                                .class(button::Class::Secondary)
+                                .on_press(Message::Louder),
+                        ),
+                );
+                if let Some(sound_error) = &self.sound_error {
+                    column = column.push(Text::new(sound_error).color(Color::RED).size(20));
+                }
+                column.into()
             }
             Phase::Setup => {
-                let mut checkboxes = Column::new().spacing(5).width(400);
-                for side_of_star in SideOfStar::all() {
+                let mut checkboxes = Column::new().spacing(5).width(600);
+                for (
+                    ((side_of_star, goal_button_state), profile_button_state),
+                    kind_button_state,
+                ) in SideOfStar::all()
+                    .into_iter()
+                    .zip(self.goal_button_states.iter_mut())
+                    .zip(self.profile_button_states.iter_mut())
+                    .zip(self.seat_kind_button_states.iter_mut())
+                {
                     let label = &format!("Side_{:?}", side_of_star);
                     let checkbox = Checkbox::new(
                         self.inner_board.players.contains(&side_of_star),
                         label,
                         move |checked| Message::PlayerToggle(side_of_star, checked),
                     );
-                    checkboxes = checkboxes.push(checkbox);
+                    let goal_label =
+                        &format!("Goal: {:?}", self.inner_board.goal_side(side_of_star));
+                    let goal_button = Button::new(goal_button_state, goal_label)
+                        .width(150)
+                        .class(button::Class::Secondary)
+                        .on_press(Message::CycleGoal(side_of_star));
+                    let profile_label = self
+                        .seat_profiles
+                        .get(&side_of_star)
+                        .map_or("Guest", String::as_str);
+                    let profile_button = Button::new(profile_button_state, profile_label)
+                        .width(150)
+                        .class(button::Class::Secondary)
+                        .on_press(Message::CycleProfile(side_of_star));
+                    let computer_sides = &self.computer_sides;
+                    let kind_label = if seat_sides(&self.inner_board, side_of_star)
+                        .iter()
+                        .any(|side| computer_sides.contains(side))
+                    {
+                        "Computer"
+                    } else {
+                        "Human"
+                    };
+                    let kind_button = Button::new(kind_button_state, kind_label)
+                        .width(110)
+                        .class(button::Class::Secondary)
+                        .on_press(Message::CycleSeatKind(side_of_star));
+                    checkboxes = checkboxes.push(
+                        Row::new()
+                            .align_items(Align::Center)
+                            .spacing(10)
+                            .push(checkbox)
+                            .push(goal_button)
+                            .push(profile_button)
+                            .push(kind_button),
+                    );
                 }
 
+                let mut seats = Column::new().spacing(5).width(300).push(Radio::new(
+                    None,
+                    "One side each",
+                    Some(self.seat_layout),
+                    Message::SeatLayout,
+                ));
+                for &(seats_count, sides_per_seat) in &SEAT_LAYOUTS {
+                    seats = seats.push(Radio::new(
+                        Some((seats_count, sides_per_seat)),
+                        &format!("{} players, {} sides each", seats_count, sides_per_seat),
+                        Some(self.seat_layout),
+                        Message::SeatLayout,
+                    ));
+                }
+                for &(turn_order, label) in &[
+                    (TurnOrder::PerSide, "Every side takes a turn"),
+                    (TurnOrder::PerSeat, "Every player takes a turn"),
+                ] {
+                    seats = seats.push(Radio::new(
+                        turn_order,
+                        label,
+                        Some(self.inner_board.turn_order),
+                        Message::TurnOrder,
+                    ));
+                }
+                let checkboxes = Row::new().spacing(20).push(checkboxes).push(seats);
+
                 let sub_heading = Text::new("Please select the players you want")
                     .horizontal_alignment(HorizontalAlignment::Center)
                     .size(30);
-                column
+
+                let rules = Column::new()
+                    .spacing(5)
+                    .width(400)
+                    .push(Checkbox::new(
+                        self.rules.blocked_goal_counts_as_filled,
+                        "Opponents blocking the goal count as filled holes",
+                        Message::BlockedGoalToggle,
+                    ))
+                    .push(Checkbox::new(
+                        self.rules.evacuate_home_after.is_some(),
+                        &format!(
+                            "Pieces must leave home after {} turns",
+                            EVACUATE_HOME_AFTER_TURNS
+                        ),
+                        Message::EvacuateHomeToggle,
+                    ))
+                    .push(Checkbox::new(
+                        self.inner_board.team_play,
+                        "Team play (4 or 6 players)",
+                        Message::TeamPlayToggle,
+                    ))
+                    .push(Checkbox::new(
+                        self.inner_board.draw_rules.repetitions.is_some(),
+                        &format!("Draw when a position comes up {} times", REPETITIONS),
+                        Message::RepetitionDrawToggle,
+                    ))
+                    .push(Checkbox::new(
+                        self.inner_board.draw_rules.no_progress_moves.is_some(),
+                        &format!("Draw after {} moves without progress", NO_PROGRESS_MOVES),
+                        Message::NoProgressDrawToggle,
+                    ));
+                let hint_budget = self.hint_budget;
+                let hint_budgets = HINT_BUDGETS.iter().fold(
+                    Row::new()
+                        .align_items(Align::Center)
+                        .spacing(10)
+                        .push(Text::new("Hints per game:")),
+                    |row, &budget| {
+                        let label = match budget {
+                            Some(count) => count.to_string(),
+                            None => String::from("Unlimited"),
+                        };
+                        row.push(Radio::new(
+                            budget,
+                            &label,
+                            Some(hint_budget),
+                            Message::HintBudget,
+                        ))
+                    },
+                );
+                let computer_bot = self.computer_bot;
+                let computer_bots = COMPUTER_BOTS.iter().fold(
+                    Row::new()
+                        .align_items(Align::Center)
+                        .spacing(10)
+                        .push(Text::new("Computer:")),
+                    |row, &bot| {
+                        row.push(Radio::new(
+                            bot,
+                            &bot.to_string(),
+                            Some(computer_bot),
+                            Message::ComputerBot,
+                        ))
+                    },
+                );
+                let rules = rules.push(hint_budgets).push(computer_bots);
+
+                column = column
                     .push(heading)
                     .push(sub_heading)
                     .push(checkboxes)
+                    .push(rules);
+                if let Some(setup_error) = setup_error {
+                    column = column.push(
+                        Text::new(&format!("Can't start: {}", setup_error))
+                            .color(Color::RED)
+                            .size(20),
+                    );
+                }
+                let edit_button =
+                    Button::new(&mut self.edit_position_button_state, "Edit position")
+                        .width(350)
+                        .class(button::Class::Secondary);
+                let edit_button = match setup_error {
+                    Some(_) => edit_button,
+                    None => edit_button.on_press(Message::EditPosition),
+                };
+                column
                     .push(next_button)
+                    .push(edit_button)
                     .push(previous_button)
+                    .into()
             }
-            Phase::Play => {
-                let mut spacer_column = Column::new()
+            Phase::Editor => {
+                let board = &self.inner_board;
+                let mut brushes = Column::new()
+                    .width(SIDE_PANEL_WIDTH as u32)
+                    .padding(20)
+                    .spacing(10)
+                    .push(Text::new("Click a hole to put").size(20))
+                    .push(Radio::new(
+                        Spot::Empty,
+                        "Nothing",
+                        Some(self.editor_brush),
+                        Message::EditorBrush,
+                    ));
+                for &player in &board.players {
+                    brushes = brushes.push(Radio::new(
+                        Spot::Player(player),
+                        &format!(
+                            "Side {:?} ({} pieces)",
+                            player,
+                            board.piece_set(player).len()
+                        ),
+                        Some(self.editor_brush),
+                        Message::EditorBrush,
+                    ));
+                }
+
+                let position_error = board.validate_position().err();
+                let mut status = Column::new().align_items(Align::Center).spacing(5).push(
+                    Row::new()
+                        .align_items(Align::Center)
+                        .spacing(10)
+                        .push(players_row("Turn: ", vec![board.turn]))
+                        .push(
+                            Button::new(&mut self.editor_turn_button_state, "Next side")
+                                .class(button::Class::Secondary)
+                                .on_press(Message::CycleEditorTurn),
+                        ),
+                );
+                status = match position_error {
+                    Some(error) => status.push(
+                        Text::new(&format!("Can't start: {}", error))
+                            .color(Color::RED)
+                            .size(20),
+                    ),
+                    None => status.push(Text::new("Ready to play").size(20)),
+                };
+                if let Some(position_exported) = &self.position_exported {
+                    status = status.push(Text::new(position_exported).size(20));
+                }
+                if let Some(reset_error) = &self.reset_error {
+                    status = status.push(Text::new(reset_error).color(Color::RED).size(20));
+                }
+
+                let start_button =
+                    Button::new(&mut self.start_edited_button_state, "Play from here").width(170);
+                let start_button = match position_error {
+                    Some(_) => start_button.class(button::Class::Secondary),
+                    None => start_button.on_press(Message::StartEditedGame),
+                };
+                let controls = Column::new()
+                    .align_items(Align::Center)
+                    .spacing(10)
+                    .push(
+                        Row::new()
+                            .spacing(10)
+                            .push(
+                                Button::new(&mut self.clear_board_button_state, "Clear board")
+                                    .width(170)
+                                    .class(button::Class::Secondary)
+                                    .on_press(Message::ClearBoard),
+                            )
+                            .push(
+                                Button::new(&mut self.reset_board_button_state, "Start position")
+                                    .width(170)
+                                    .class(button::Class::Secondary)
+                                    .on_press(Message::ResetBoard),
+                            ),
+                    )
+                    .push(
+                        Row::new().spacing(10).push(start_button).push(
+                            Button::new(&mut self.export_position_button_state, "Export")
+                                .width(170)
+                                .class(button::Class::Secondary)
+                                .on_press(Message::ExportPosition),
+                        ),
+                    )
+                    .push(previous_button);
+
+                let spacer_column = Column::new()
                     .justify_content(Justify::SpaceBetween)
                     .align_items(Align::Center)
-                    .spacing((window.height() * 0.8) as u16);
-                let turn = Row::new()
-                    .justify_content(Justify::Center)
+                    .spacing((window.height() * 0.6) as u16)
+                    .push(status)
+                    .push(controls);
+                let center = Column::new()
+                    .align_items(Align::Center)
+                    .spacing(5)
+                    .width(center_width)
+                    .push(heading.size(40))
+                    .push(spacer_column);
+                Row::new().push(brushes).push(center).into()
+            }
+            Phase::Leaderboard => {
+                let sub_heading = Text::new("Leaderboard")
+                    .horizontal_alignment(HorizontalAlignment::Center)
+                    .size(30);
+                column = column.push(heading).push(sub_heading);
+
+                let profiles = &self.profiles;
+                self.profile_color_button_states
+                    .resize(profiles.profiles.len(), button::State::default());
+                let mut color_button_states: Vec<Option<&mut button::State>> = self
+                    .profile_color_button_states
+                    .iter_mut()
+                    .map(Some)
+                    .collect();
+                let mut table = Column::new().spacing(5);
+                for (rank, profile) in profiles
+                    .leaderboard()
+                    .into_iter()
+                    .enumerate()
+                    .take(LEADERBOARD_ROWS)
+                {
+                    let index = profiles
+                        .profiles
+                        .iter()
+                        .position(|other| other.name == profile.name)
+                        .expect("the leaderboard lists the profiles");
+                    let color_button_state = color_button_states[index]
+                        .take()
+                        .expect("every profile is listed once");
+                    table = table.push(
+                        Row::new()
+                            .align_items(Align::Center)
+                            .spacing(10)
+                            .push(Text::new(&format!("{}.", rank + 1)).width(40))
+                            .push(
+                                Text::new(&profile.name)
+                                    .color(profile.color.color())
+                                    .width(220),
+                            )
+                            .push(Text::new(&format!("Elo {:.0}", profile.elo)).width(110))
+                            .push(
+                                Text::new(&format!(
+                                    "Glicko {:.0} \u{b1} {:.0}",
+                                    profile.glicko.rating, profile.glicko.deviation
+                                ))
+                                .width(200),
+                            )
+                            .push(
+                                Text::new(&format!("{} of {} won", profile.wins, profile.games))
+                                    .width(130),
+                            )
+                            .push(
+                                Button::new(color_button_state, "Color")
+                                    .class(button::Class::Secondary)
+                                    .on_press(Message::CycleProfileColor(index)),
+                            ),
+                    );
+                }
+                if profiles.profiles.is_empty() {
+                    table = table.push(Text::new("No profiles yet, add one below."));
+                }
+
+                let new_profile = Row::new()
                     .align_items(Align::Center)
-                    .push(Text::new("Turn: ").size(25))
+                    .spacing(10)
+                    .push(Text::new(&format!("New player: {}_", self.new_profile_name)).width(400))
                     .push(
-                        Text::new(&format!("{:?}", self.inner_board.turn))
-                            .color(self.inner_board.turn.color())
-                            .size(40),
+                        Button::new(&mut self.add_profile_button_state, "Add")
+                            .width(150)
+                            .on_press(Message::AddProfile),
                     );
+                column = column
+                    .push(table)
+                    .push(Text::new("Type a name and press Enter or Add.").size(16))
+                    .push(new_profile);
+                if let Some(profiles_error) = &self.profiles_error {
+                    column = column.push(Text::new(profiles_error).color(Color::RED).size(20));
+                }
+                column.push(previous_button).into()
+            }
+            Phase::History => {
+                let archived_games = &self.archive.games;
+                let games = history_games(
+                    archived_games,
+                    self.history_player.as_deref(),
+                    self.history_players_count,
+                );
+                let pages = games.len().div_ceil(HISTORY_ROWS).max(1);
+                let page = self.history_page.min(pages - 1);
 
-                spacer_column = spacer_column.push(turn).push(previous_button);
-                let heading = heading.size(40);
-                column.spacing(5).push(heading).push(spacer_column)
+                let player_label = format!(
+                    "Player: {}",
+                    self.history_player.as_deref().unwrap_or("All")
+                );
+                let players_count_label = match self.history_players_count {
+                    Some(count) => format!("Players: {}", count),
+                    None => String::from("Players: All"),
+                };
+                let filters = Row::new()
+                    .spacing(10)
+                    .push(
+                        Button::new(&mut self.history_player_button_state, &player_label)
+                            .width(300)
+                            .class(button::Class::Secondary)
+                            .on_press(Message::CycleHistoryPlayer),
+                    )
+                    .push(
+                        Button::new(
+                            &mut self.history_players_count_button_state,
+                            &players_count_label,
+                        )
+                        .width(200)
+                        .class(button::Class::Secondary)
+                        .on_press(Message::CycleHistoryPlayersCount),
+                    );
+
+                let mut table = Column::new().spacing(5);
+                for (&index, open_replay_button_state) in games
+                    .iter()
+                    .skip(page * HISTORY_ROWS)
+                    .zip(self.open_replay_button_states.iter_mut())
+                {
+                    let game = &archived_games[index];
+                    let sides =
+                        game.players
+                            .iter()
+                            .fold(Row::new().spacing(5).width(120), |row, &side| {
+                                row.push(
+                                    Text::new(&format!("{:?}", side))
+                                        .color(side.color())
+                                        .size(20),
+                                )
+                            });
+                    let seconds = game.duration.as_secs();
+                    table = table.push(
+                        Row::new()
+                            .align_items(Align::Center)
+                            .spacing(10)
+                            .push(Text::new(&game.finished_date()).size(20).width(170))
+                            .push(sides)
+                            .push(
+                                Text::new(&format!("{} moves", game.moves.len()))
+                                    .size(20)
+                                    .width(110),
+                            )
+                            .push(
+                                Text::new(&format!("{}:{:02}", seconds / 60, seconds % 60))
+                                    .size(20)
+                                    .width(70),
+                            )
+                            .push(Text::new(&game_result(game)).size(20).width(300))
+                            .push(
+                                Button::new(open_replay_button_state, "Replay")
+                                    .class(button::Class::Secondary)
+                                    .on_press(Message::OpenReplay(index)),
+                            ),
+                    );
+                }
+                if games.is_empty() {
+                    table = table.push(Text::new("No finished games to show."));
+                }
+
+                let newer_button = Button::new(&mut self.newer_games_button_state, "Newer")
+                    .width(150)
+                    .class(button::Class::Secondary);
+                let older_button = Button::new(&mut self.older_games_button_state, "Older")
+                    .width(150)
+                    .class(button::Class::Secondary);
+                let pager = Row::new()
+                    .align_items(Align::Center)
+                    .spacing(20)
+                    .push(if page > 0 {
+                        newer_button.on_press(Message::NewerGames)
+                    } else {
+                        newer_button
+                    })
+                    .push(Text::new(&format!("Page {} of {}", page + 1, pages)))
+                    .push(if page + 1 < pages {
+                        older_button.on_press(Message::OlderGames)
+                    } else {
+                        older_button
+                    });
+
+                let stats_row = |cells: [String; 5]| {
+                    let widths = [220, 80, 80, 130, 190];
+                    cells
+                        .iter()
+                        .zip(&widths)
+                        .fold(Row::new().spacing(10), |row, (cell, &width)| {
+                            row.push(Text::new(cell).size(20).width(width))
+                        })
+                };
+                let mut stats: Vec<(String, archive::PlayerStats)> =
+                    archive::player_stats(games.iter().map(|&index| &archived_games[index]))
+                        .into_iter()
+                        .collect();
+                stats.sort_by_key(|(_name, player_stats)| std::cmp::Reverse(player_stats.games));
+                let stats = stats.into_iter().take(HISTORY_STATS_ROWS).fold(
+                    Column::new().spacing(5).push(stats_row([
+                        String::from("Player"),
+                        String::from("Games"),
+                        String::from("Won"),
+                        String::from("Avg. moves"),
+                        String::from("Avg. longest jump"),
+                    ])),
+                    |column, (name, player_stats)| {
+                        column.push(stats_row([
+                            name,
+                            player_stats.games.to_string(),
+                            player_stats.wins.to_string(),
+                            format!("{:.1}", player_stats.average_moves()),
+                            format!("{:.1}", player_stats.average_longest_jump()),
+                        ]))
+                    },
+                );
+
+                column = column
+                    .push(heading.size(50))
+                    .push(filters)
+                    .push(table)
+                    .push(pager)
+                    .push(stats);
+                if let Some(archive_error) = &self.archive_error {
+                    column = column.push(Text::new(archive_error).color(Color::RED).size(20));
+                }
+                column.push(previous_button).into()
             }
-        };
+            Phase::Replay => {
+                let (game, moves_shown) = match &self.replay {
+                    Some(replay) => (&self.archive.games[replay.game], replay.moves_shown),
+                    None => return column.push(previous_button).into(),
+                };
+                let total_moves = game.moves.len();
+
+                let mut status = Column::new()
+                    .align_items(Align::Center)
+                    .spacing(5)
+                    .push(Text::new(&format!("Played {}", game.finished_date())).size(25))
+                    .push(Text::new(&format!("Move {} of {}", moves_shown, total_moves)).size(25));
+                if let Some(last_move) = moves_shown.checked_sub(1) {
+                    let played = game.moves[last_move];
+                    let jumps = match game.jumps.get(last_move) {
+                        Some(&jumps) if jumps > 1 => format!(", {} jumps", jumps),
+                        _ => String::new(),
+                    };
+                    status = status.push(
+                        Text::new(&format!("{}{}", played, jumps))
+                            .color(played.player.color())
+                            .size(25),
+                    );
+                }
+                if moves_shown == total_moves {
+                    status = status.push(Text::new(&game_result(game)).size(25));
+                }
+
+                let targets = [
+                    ("|<", 0),
+                    ("<", moves_shown.saturating_sub(1)),
+                    (">", (moves_shown + 1).min(total_moves)),
+                    (">|", total_moves),
+                ];
+                let mut steps = Row::new().spacing(10);
+                for (&(label, target), state) in
+                    targets.iter().zip(self.replay_button_states.iter_mut())
+                {
+                    let step = Button::new(state, label)
+                        .width(80)
+                        .class(button::Class::Secondary);
+                    steps = steps.push(if target != moves_shown {
+                        step.on_press(Message::ReplayTo(target))
+                    } else {
+                        step
+                    });
+                }
+                let controls = Column::new()
+                    .align_items(Align::Center)
+                    .spacing(10)
+                    .push(steps)
+                    .push(previous_button);
+
+                let spacer_column = Column::new()
+                    .justify_content(Justify::SpaceBetween)
+                    .align_items(Align::Center)
+                    .spacing((window.height() * 0.7) as u16)
+                    .push(status)
+                    .push(controls);
+                let center = Column::new()
+                    .align_items(Align::Center)
+                    .spacing(5)
+                    .width(center_width)
+                    .push(heading.size(40))
+                    .push(spacer_column);
+                Row::new()
+                    .push(Column::new().width(SIDE_PANEL_WIDTH as u32))
+                    .push(center)
+                    .into()
+            }
+            Phase::Puzzles => {
+                let progress = &self.puzzle_progress;
+                let solved_count = self
+                    .puzzles
+                    .iter()
+                    .filter(|puzzle| progress.is_solved(puzzle))
+                    .count();
+                let sub_heading = Text::new(&format!(
+                    "Puzzles: {} of {} solved",
+                    solved_count,
+                    self.puzzles.len()
+                ))
+                .horizontal_alignment(HorizontalAlignment::Center)
+                .size(30);
+
+                let mut table = Column::new().spacing(5);
+                for (index, (puzzle, open_puzzle_button_state)) in self
+                    .puzzles
+                    .iter()
+                    .zip(self.open_puzzle_button_states.iter_mut())
+                    .enumerate()
+                {
+                    let tries = progress.tries.get(&puzzle.name).copied().unwrap_or(0);
+                    let (status, status_color, button_label) = if progress.is_solved(puzzle) {
+                        ("Solved", Color::GREEN, "Play again")
+                    } else if tries > 0 {
+                        ("Tried", Color::WHITE, "Try again")
+                    } else {
+                        ("New", Color::WHITE, "Play")
+                    };
+                    table = table.push(
+                        Row::new()
+                            .align_items(Align::Center)
+                            .spacing(10)
+                            .push(Text::new(&puzzle.name).size(20).width(220))
+                            .push(Text::new(&puzzle.goal.to_string()).size(20).width(380))
+                            .push(Text::new(status).color(status_color).size(20).width(90))
+                            .push(
+                                Button::new(open_puzzle_button_state, button_label)
+                                    .width(150)
+                                    .class(button::Class::Secondary)
+                                    .on_press(Message::OpenPuzzle(index)),
+                            ),
+                    );
+                }
+
+                column = column.push(heading).push(sub_heading).push(table);
+                if let Some(puzzles_error) = &self.puzzles_error {
+                    column = column.push(Text::new(puzzles_error).color(Color::RED).size(20));
+                }
+                column.push(previous_button).into()
+            }
+            Phase::Puzzle => {
+                let puzzle_state = match &self.puzzle {
+                    Some(puzzle_state) => puzzle_state,
+                    None => return column.push(previous_button).into(),
+                };
+                let index = puzzle_state.index;
+                let attempt = &puzzle_state.attempt;
+
+                let mut status = Column::new()
+                    .align_items(Align::Center)
+                    .spacing(5)
+                    .push(Text::new(&self.puzzles[index].name).size(30))
+                    .push(
+                        Text::new(&attempt.goal.to_string())
+                            .color(attempt.board.turn.color())
+                            .size(25),
+                    );
+                let feedback = match puzzle_state.last_try {
+                    Some(Attempted::Wrong) => Some(("Not that one, try again", Color::RED)),
+                    Some(Attempted::Correct) => Some(("Right, keep going", Color::GREEN)),
+                    Some(Attempted::Solved) if puzzle_state.answer_shown => {
+                        Some(("Solved with the answer shown", Color::WHITE))
+                    }
+                    Some(Attempted::Solved) => Some(("Solved!", Color::GREEN)),
+                    Some(Attempted::Illegal) | None => None,
+                };
+                if let Some((feedback, color)) = feedback {
+                    status = status.push(Text::new(feedback).color(color).size(25));
+                }
+                if attempt.mistakes > 0 {
+                    status = status
+                        .push(Text::new(&format!("Wrong moves: {}", attempt.mistakes)).size(20));
+                }
+                if let Some(puzzles_error) = &self.puzzles_error {
+                    status = status.push(Text::new(puzzles_error).color(Color::RED).size(20));
+                }
 
-        column.into()
+                let show_answer_button =
+                    Button::new(&mut self.show_answer_button_state, "Show answer")
+                        .width(170)
+                        .class(button::Class::Secondary);
+                let next_puzzle_button =
+                    Button::new(&mut self.next_puzzle_button_state, "Next puzzle")
+                        .width(350)
+                        .class(button::Class::Secondary);
+                let controls = Column::new()
+                    .align_items(Align::Center)
+                    .spacing(10)
+                    .push(
+                        Row::new()
+                            .spacing(10)
+                            .push(if attempt.solved {
+                                show_answer_button
+                            } else {
+                                show_answer_button.on_press(Message::ShowAnswer)
+                            })
+                            .push(
+                                Button::new(&mut self.retry_puzzle_button_state, "Start over")
+                                    .width(170)
+                                    .class(button::Class::Secondary)
+                                    .on_press(Message::OpenPuzzle(index)),
+                            ),
+                    )
+                    .push(if index + 1 < self.puzzles.len() {
+                        next_puzzle_button.on_press(Message::OpenPuzzle(index + 1))
+                    } else {
+                        next_puzzle_button
+                    })
+                    .push(previous_button);
+
+                let spacer_column = Column::new()
+                    .justify_content(Justify::SpaceBetween)
+                    .align_items(Align::Center)
+                    .spacing((window.height() * 0.6) as u16)
+                    .push(status)
+                    .push(controls);
+                let center = Column::new()
+                    .align_items(Align::Center)
+                    .spacing(5)
+                    .width(center_width)
+                    .push(heading.size(40))
+                    .push(spacer_column);
+                Row::new()
+                    .push(Column::new().width(SIDE_PANEL_WIDTH as u32))
+                    .push(center)
+                    .into()
+            }
+            Phase::Tutorial => {
+                let tutorial = match &self.tutorial {
+                    Some(tutorial) => tutorial,
+                    None => return column.push(previous_button).into(),
+                };
+                let lesson = tutorial.lesson();
+                let lessons_count = tutorial.lessons.len();
+                let index = tutorial.index;
+
+                let mut status = Column::new()
+                    .align_items(Align::Center)
+                    .spacing(5)
+                    .push(
+                        Text::new(&format!(
+                            "Lesson {} of {}: {}",
+                            index + 1,
+                            lessons_count,
+                            lesson.title
+                        ))
+                        .size(30),
+                    )
+                    .push(Text::new(lesson.text).size(20).width(600));
+                if tutorial.missed {
+                    status = status.push(
+                        Text::new("Drop the piece in the highlighted hole")
+                            .color(Color::RED)
+                            .size(20),
+                    );
+                }
+                if tutorial.done && lesson.task.is_some() {
+                    status = status.push(
+                        Text::new(lesson.done_text)
+                            .color(Color::GREEN)
+                            .size(20)
+                            .width(600),
+                    );
+                }
+
+                let back_button = Button::new(&mut self.previous_lesson_button_state, "Back")
+                    .width(170)
+                    .class(button::Class::Secondary);
+                let last_lesson = index + 1 == lessons_count;
+                let next_button = Button::new(
+                    &mut self.next_lesson_button_state,
+                    if last_lesson { "Done" } else { "Next" },
+                )
+                .width(170);
+                let next_button = match (tutorial.done, last_lesson) {
+                    (false, _) => next_button.class(button::Class::Secondary),
+                    (true, false) => next_button.on_press(Message::OpenLesson(index + 1)),
+                    (true, true) => next_button.on_press(Message::Previous),
+                };
+                let controls = Column::new()
+                    .align_items(Align::Center)
+                    .spacing(10)
+                    .push(
+                        Row::new()
+                            .spacing(10)
+                            .push(match index.checked_sub(1) {
+                                Some(previous) => {
+                                    back_button.on_press(Message::OpenLesson(previous))
+                                }
+                                None => back_button,
+                            })
+                            .push(next_button),
+                    )
+                    .push(previous_button);
+
+                let spacer_column = Column::new()
+                    .justify_content(Justify::SpaceBetween)
+                    .align_items(Align::Center)
+                    .spacing((window.height() * 0.5) as u16)
+                    .push(status)
+                    .push(controls);
+                let center = Column::new()
+                    .align_items(Align::Center)
+                    .spacing(5)
+                    .width(center_width)
+                    .push(heading.size(40))
+                    .push(spacer_column);
+                Row::new()
+                    .push(Column::new().width(SIDE_PANEL_WIDTH as u32))
+                    .push(center)
+                    .into()
+            }
+            Phase::Solitaire => {
+                let game = match &self.solitaire {
+                    Some(game) => game,
+                    None => return column.push(previous_button).into(),
+                };
+                let challenge = game.challenge;
+                let mut options = Column::new()
+                    .width(SIDE_PANEL_WIDTH as u32)
+                    .padding(20)
+                    .spacing(10)
+                    .push(
+                        Row::new()
+                            .align_items(Align::Center)
+                            .spacing(10)
+                            .push(players_row("Side: ", vec![challenge.side]))
+                            .push(
+                                Button::new(&mut self.solitaire_side_button_state, "Next side")
+                                    .class(button::Class::Secondary)
+                                    .on_press(Message::CycleSolitaireSide),
+                            ),
+                    )
+                    .push(Text::new("Obstacles").size(20));
+                for &obstacles in &Obstacles::all() {
+                    options = options.push(Radio::new(
+                        obstacles,
+                        obstacles.name(),
+                        Some(challenge.obstacles),
+                        Message::SolitaireObstacles,
+                    ));
+                }
+
+                let moves = game.board.history.len();
+                let par = match self.solitaire_pars.get(&challenge.obstacles) {
+                    Some(Some(solution)) if solution.is_optimal() => {
+                        format!("Par: {} moves", solution.lower_bound)
+                    }
+                    Some(Some(solution)) => format!(
+                        "Par: at least {} moves, the engine takes {}",
+                        solution.lower_bound,
+                        solution.route.moves.len()
+                    ),
+                    Some(None) => String::from("Par: no route found"),
+                    None => String::from("Par: working it out..."),
+                };
+                let best = match self.solitaire_records.best(challenge) {
+                    Some(best) => format!("Your best: {} moves", best),
+                    None => String::from("Your best: none yet"),
+                };
+                let mut status = Column::new()
+                    .align_items(Align::Center)
+                    .spacing(5)
+                    .push(
+                        Text::new("Fill the opposite triangle in as few moves as you can").size(25),
+                    )
+                    .push(Text::new(&format!("Moves: {}", moves)).size(25))
+                    .push(Text::new(&par).size(20))
+                    .push(Text::new(&best).size(20));
+                if game.board.is_over() {
+                    status = status.push(
+                        Text::new(&format!("Across in {} moves", moves))
+                            .color(challenge.side.color())
+                            .size(30),
+                    );
+                    if game.new_best {
+                        status = status.push(Text::new("New best!").color(Color::GREEN).size(25));
+                    }
+                }
+                if let Some(solitaire_error) = &self.solitaire_error {
+                    status = status.push(Text::new(solitaire_error).color(Color::RED).size(20));
+                }
+
+                let controls = Column::new()
+                    .align_items(Align::Center)
+                    .spacing(10)
+                    .push(
+                        Button::new(&mut self.restart_solitaire_button_state, "Start over")
+                            .width(350)
+                            .class(button::Class::Secondary)
+                            .on_press(Message::RestartSolitaire),
+                    )
+                    .push(previous_button);
+                let spacer_column = Column::new()
+                    .justify_content(Justify::SpaceBetween)
+                    .align_items(Align::Center)
+                    .spacing((window.height() * 0.5) as u16)
+                    .push(status)
+                    .push(controls);
+                let center = Column::new()
+                    .align_items(Align::Center)
+                    .spacing(5)
+                    .width(center_width)
+                    .push(heading.size(40))
+                    .push(spacer_column);
+                Row::new().push(options).push(center).into()
+            }
+            Phase::Play => {
+                let mut spacer_column = Column::new()
+                    .justify_content(Justify::SpaceBetween)
+                    .align_items(Align::Center)
+                    .spacing((window.height() * 0.7) as u16);
+                let board = &self.inner_board;
+                let outcome = &board.outcome;
+                let mut status = Column::new().align_items(Align::Center).spacing(5);
+                status = if let Some(draw) = outcome.draw {
+                    status.push(Text::new(&format!("Draw by {}", draw.describe())).size(40))
+                } else if board.is_over() {
+                    let winners = outcome.winning_team().cloned().unwrap_or_default();
+                    status.push(players_row("Winner: ", winners))
+                } else {
+                    status = status.push(players_row("Turn: ", vec![board.turn]));
+                    if let Some(name) = self.seat_profiles.get(&board.turn) {
+                        status = status.push(Text::new(name).size(25));
+                    }
+                    if let Some(seat) = board.seat_of(board.turn) {
+                        if seat.sides.len() > 1 {
+                            status = status.push(players_row("Player: ", seat.sides.clone()));
+                        }
+                    }
+                    if board.team_play {
+                        status = status.push(players_row("Team: ", board.team_of(board.turn)));
+                    }
+                    status
+                };
+                if let Some((moves_shown, _)) = &self.viewed_position {
+                    status = status
+                        .push(Text::new(&format!("Looking back at move {}", moves_shown)).size(25));
+                }
+
+                let draw_controls = match self.draw_offer {
+                    _ if board.is_over() => Row::new(),
+                    Some(offering_player) => Row::new()
+                        .align_items(Align::Center)
+                        .spacing(10)
+                        .push(
+                            Text::new(&format!("{:?} offers a draw", offering_player))
+                                .color(offering_player.color())
+                                .size(25),
+                        )
+                        .push(
+                            Button::new(&mut self.accept_draw_button_state, "Accept")
+                                .width(150)
+                                .class(button::Class::Positive)
+                                .on_press(Message::AcceptDraw),
+                        )
+                        .push(
+                            Button::new(&mut self.decline_draw_button_state, "Decline")
+                                .width(150)
+                                .class(button::Class::Secondary)
+                                .on_press(Message::DeclineDraw),
+                        ),
+                    None => Row::new().push(
+                        Button::new(&mut self.offer_draw_button_state, "Offer draw")
+                            .width(350)
+                            .class(button::Class::Secondary)
+                            .on_press(Message::OfferDraw),
+                    ),
+                };
+                let hint_label = match self.hints_left {
+                    Some(hints_left) => format!("Hint ({} left)", hints_left),
+                    None => String::from("Hint"),
+                };
+                let is_human = board
+                    .seat_of(board.turn)
+                    .is_none_or(|seat| seat.kind == SeatKind::Human);
+                let hint_button = Button::new(&mut self.hint_button_state, &hint_label)
+                    .width(350)
+                    .class(button::Class::Secondary);
+                let hint_button = if is_human
+                    && !board.is_over()
+                    && self.hints_left != Some(0)
+                    && self.hint_search.is_none()
+                {
+                    hint_button.on_press(Message::Hint)
+                } else {
+                    hint_button
+                };
+                let mut controls = Column::new().align_items(Align::Center).spacing(10);
+                // the analysis replays the game from the start position
+                controls = if board.is_over() && self.edited_position {
+                    controls
+                } else if board.is_over() {
+                    let analyze_button =
+                        Button::new(&mut self.analyze_button_state, "Analyze game")
+                            .width(350)
+                            .class(button::Class::Secondary);
+                    controls.push(match self.analyzing {
+                        Some(_) => analyze_button,
+                        None => analyze_button.on_press(Message::Analyze),
+                    })
+                } else {
+                    controls.push(hint_button).push(draw_controls)
+                };
+                let controls = controls.push(previous_button);
+
+                spacer_column = spacer_column.push(status).push(controls);
+                let heading = heading.size(40);
+                let center = Column::new()
+                    .align_items(Align::Center)
+                    .spacing(5)
+                    .width(center_width)
+                    .push(heading)
+                    .push(spacer_column);
+
+                let mut row = Row::new()
+                    .push(
+                        standings_panel(&self.inner_board, self.show_standings)
+                            .push(Checkbox::new(
+                                self.show_coordinates,
+                                "Show coordinates",
+                                Message::CoordinatesToggle,
+                            ))
+                            .push(Checkbox::new(
+                                self.show_trails,
+                                "Show last moves",
+                                Message::TrailsToggle,
+                            )),
+                    )
+                    .push(center);
+                row = match (&self.analysis, &self.analyzing) {
+                    (Some(analysis), _) => row.push(analysis_panel(
+                        analysis,
+                        self.analysis_saved.as_deref(),
+                        &mut self.save_analysis_button_state,
+                    )),
+                    (None, Some(_)) => row.push(
+                        Column::new()
+                            .width(SIDE_PANEL_WIDTH as u32)
+                            .padding(20)
+                            .spacing(10)
+                            .push(Text::new("Analysis").size(30))
+                            .push(Text::new("Going over every move...").size(16)),
+                    ),
+                    (None, None) => row.push(moves_panel(
+                        &self.inner_board.history,
+                        self.move_list_page,
+                        self.viewed_position
+                            .as_ref()
+                            .map(|(moves_shown, _)| *moves_shown),
+                        &mut self.view_move_button_states,
+                        &mut self.earlier_moves_button_state,
+                        &mut self.later_moves_button_state,
+                        &mut self.back_to_live_button_state,
+                    )),
+                };
+                row.into()
+            }
+        }
     }
 
     fn react(&mut self, message: Self::Message, _window: &mut Window) {
         match message {
             Message::Next => {
                 if self.phase == Phase::Setup {
-                    self.inner_board.setup_players();
+                    self.inner_board.set_rules(self.rules);
+                    if self.inner_board.setup_players().is_ok() {
+                        self.start_play(false);
+                    }
+                    return;
                 };
                 self.phase = self.phase.next();
             }
+            Message::EditPosition => {
+                self.inner_board.set_rules(self.rules);
+                if self.inner_board.setup_players().is_ok() {
+                    self.editor_brush = Spot::Player(self.inner_board.turn);
+                    self.position_exported = None;
+                    self.reset_error = None;
+                    self.phase = Phase::Editor;
+                }
+            }
+            Message::EditorBrush(brush) => {
+                self.editor_brush = brush;
+            }
+            Message::CycleEditorTurn => {
+                // the next playing side around the star, even if the turn
+                // was left with a side that isn't playing
+                let board = &mut self.inner_board;
+                let mut turn = board.turn.forward();
+                for _ in 0..6 {
+                    if board.players.contains(&turn) {
+                        board.turn = turn;
+                        break;
+                    }
+                    turn = turn.forward();
+                }
+            }
+            Message::ClearBoard => {
+                self.inner_board.clear_pieces();
+            }
+            Message::ResetBoard => {
+                // the board is left as it is when the setup isn't valid
+                self.reset_error = self
+                    .inner_board
+                    .setup_players()
+                    .err()
+                    .map(|error| format!("Can't reset: {}", error));
+            }
+            Message::StartEditedGame => {
+                if self.inner_board.start_from_position().is_ok() {
+                    self.start_play(true);
+                }
+            }
+            Message::ExportPosition => {
+                self.export_position();
+            }
             Message::Previous => {
                 self.phase = self.phase.previous();
             }
@@ -399,11 +2795,488 @@ impl UserInterface for BoardGame {
                 } else {
                     self.inner_board.players.remove(&side);
                 }
+                self.seat_layout = None;
+                let seats = single_side_seats(&self.inner_board.players);
+                self.inner_board.set_seats(seats);
+            }
+            Message::CycleGoal(side) => {
+                let mut goal = self.inner_board.goal_side(side).forward();
+                if goal == side {
+                    goal = goal.forward();
+                }
+                self.inner_board.set_goal(side, goal);
+            }
+            Message::SeatLayout(layout) => {
+                self.seat_layout = layout;
+                let seats = match layout {
+                    Some((seats_count, sides_per_seat)) => {
+                        gen_seats(seats_count, sides_per_seat).unwrap_or_default()
+                    }
+                    None => single_side_seats(&self.inner_board.players),
+                };
+                self.inner_board.set_seats(seats);
+            }
+            Message::TurnOrder(turn_order) => {
+                self.inner_board.turn_order = turn_order;
+            }
+            Message::CycleSeatKind(side) => {
+                let sides = seat_sides(&self.inner_board, side);
+                let computer = !sides.iter().any(|side| self.computer_sides.contains(side));
+                for side in sides {
+                    if computer {
+                        self.computer_sides.insert(side);
+                    } else {
+                        self.computer_sides.remove(&side);
+                    }
+                }
+            }
+            Message::ComputerBot(bot) => {
+                self.computer_bot = bot;
+            }
+            Message::BlockedGoalToggle(checked) => {
+                self.rules.blocked_goal_counts_as_filled = checked;
+            }
+            Message::TeamPlayToggle(checked) => {
+                self.inner_board.team_play = checked;
+            }
+            Message::RepetitionDrawToggle(checked) => {
+                self.inner_board.draw_rules.repetitions =
+                    if checked { Some(REPETITIONS) } else { None };
+            }
+            Message::NoProgressDrawToggle(checked) => {
+                self.inner_board.draw_rules.no_progress_moves = if checked {
+                    Some(NO_PROGRESS_MOVES)
+                } else {
+                    None
+                };
+            }
+            Message::StandingsToggle(checked) => {
+                self.show_standings = checked;
+            }
+            Message::CoordinatesToggle(checked) => {
+                self.show_coordinates = checked;
+            }
+            Message::TrailsToggle(checked) => {
+                self.show_trails = checked;
+            }
+            Message::MuteToggle(checked) => {
+                self.sounds.settings.muted = checked;
+                self.save_sound_settings();
+            }
+            Message::Quieter => {
+                self.sounds.settings.quieter();
+                self.save_sound_settings();
+            }
+            Message::Louder => {
+                self.sounds.settings.louder();
+                self.save_sound_settings();
+            }
+            Message::ViewPosition(moves_shown) => {
+                self.view_position(moves_shown);
+            }
+            Message::EarlierMoves => {
+                self.move_list_page += 1;
+            }
+            Message::LaterMoves => {
+                self.move_list_page = self.move_list_page.saturating_sub(1);
+            }
+            Message::BackToLive => {
+                self.viewed_position = None;
+            }
+            Message::OfferDraw => {
+                self.draw_offer = Some(self.inner_board.turn);
+            }
+            Message::AcceptDraw => {
+                self.inner_board.agree_draw();
+                self.draw_offer = None;
+            }
+            Message::DeclineDraw => {
+                self.draw_offer = None;
+            }
+            Message::Hint => {
+                self.show_hint();
+            }
+            Message::HintBudget(budget) => {
+                self.hint_budget = budget;
+            }
+            Message::Analyze => {
+                self.analyze();
+            }
+            Message::SaveAnalysis => {
+                self.save_analysis();
+            }
+            Message::ShowHistory => {
+                self.history_page = 0;
+                self.phase = Phase::History;
+            }
+            Message::CycleHistoryPlayer => {
+                let names: Vec<String> = archive::player_stats(&self.archive.games)
+                    .into_keys()
+                    .collect();
+                self.history_player = match &self.history_player {
+                    Some(name) => names
+                        .iter()
+                        .position(|other| other == name)
+                        .and_then(|position| names.get(position + 1))
+                        .cloned(),
+                    None => names.first().cloned(),
+                };
+                self.history_page = 0;
+            }
+            Message::CycleHistoryPlayersCount => {
+                self.history_players_count = match self.history_players_count {
+                    None => Some(2),
+                    Some(count) if count < 6 => Some(count + 1),
+                    Some(_) => None,
+                };
+                self.history_page = 0;
+            }
+            Message::NewerGames => {
+                self.history_page = self.history_page.saturating_sub(1);
+            }
+            Message::OlderGames => {
+                self.history_page += 1;
+            }
+            Message::OpenReplay(game) => {
+                self.show_replay(game, 0);
+            }
+            Message::ReplayTo(moves_shown) => {
+                if let Some(game) = self.replay.as_ref().map(|replay| replay.game) {
+                    self.show_replay(game, moves_shown);
+                }
+            }
+            Message::ShowTutorial => {
+                let lessons = tutorial::lessons();
+                let board = lessons[0].board.clone();
+                let mut tutorial = TutorialState {
+                    lessons,
+                    index: 0,
+                    board,
+                    done: false,
+                    missed: false,
+                };
+                tutorial.open_lesson(0);
+                self.tutorial = Some(tutorial);
+                self.hint = None;
+                self.lifted_piece = None;
+                self.phase = Phase::Tutorial;
+            }
+            Message::OpenLesson(index) => {
+                if let Some(tutorial) = self.tutorial.as_mut() {
+                    tutorial.open_lesson(index);
+                }
+                self.lifted_piece = None;
+            }
+            Message::ShowSolitaire => {
+                let challenge = match &self.solitaire {
+                    Some(game) => game.challenge,
+                    None => Challenge {
+                        side: Player::A,
+                        obstacles: Obstacles::None,
+                    },
+                };
+                self.start_solitaire(challenge);
+            }
+            Message::SolitaireObstacles(obstacles) => {
+                if let Some(game) = &self.solitaire {
+                    self.start_solitaire(Challenge {
+                        obstacles,
+                        ..game.challenge
+                    });
+                }
+            }
+            Message::CycleSolitaireSide => {
+                if let Some(game) = &self.solitaire {
+                    self.start_solitaire(Challenge {
+                        side: game.challenge.side.forward(),
+                        ..game.challenge
+                    });
+                }
+            }
+            Message::RestartSolitaire => {
+                if let Some(game) = &self.solitaire {
+                    self.start_solitaire(game.challenge);
+                }
+            }
+            Message::ShowPuzzles => {
+                self.phase = Phase::Puzzles;
+            }
+            Message::OpenPuzzle(index) => {
+                self.open_puzzle(index);
+            }
+            Message::ShowAnswer => {
+                if let Some(puzzle) = self.puzzle.as_mut() {
+                    self.hint = puzzle.attempt.solutions().first().copied();
+                    puzzle.answer_shown = true;
+                }
+            }
+            Message::ShowLeaderboard => {
+                self.phase = Phase::Leaderboard;
+            }
+            Message::AddProfile => {
+                self.add_profile();
+            }
+            Message::CycleProfile(side) => {
+                let names: Vec<&String> = self
+                    .profiles
+                    .profiles
+                    .iter()
+                    .map(|profile| &profile.name)
+                    .collect();
+                let next = match self.seat_profiles.get(&side) {
+                    Some(name) => names
+                        .iter()
+                        .position(|&other| other == name)
+                        .and_then(|position| names.get(position + 1)),
+                    None => names.first(),
+                };
+                match next {
+                    Some(&name) => self.seat_profiles.insert(side, name.clone()),
+                    None => self.seat_profiles.remove(&side),
+                };
+            }
+            Message::CycleProfileColor(index) => {
+                if let Some(profile) = self.profiles.profiles.get_mut(index) {
+                    profile.color = profile.color.forward();
+                    self.save_profiles();
+                }
+            }
+            Message::EvacuateHomeToggle(checked) => {
+                self.rules.evacuate_home_after = if checked {
+                    Some(EVACUATE_HOME_AFTER_TURNS)
+                } else {
+                    None
+                };
             }
         }
     }
 }
 
+/// Every player's progress towards their goal and estimated chance to
+/// win, hidden unless asked for so it doesn't spoil casual games.
+fn standings_panel<'a>(board: &Board, show_standings: bool) -> Column<'a, Message> {
+    let mut panel = Column::new()
+        .width(SIDE_PANEL_WIDTH as u32)
+        .padding(20)
+        .spacing(10)
+        .push(Checkbox::new(
+            show_standings,
+            "Show standings",
+            Message::StandingsToggle,
+        ));
+    if !show_standings {
+        return panel;
+    }
+
+    let win_chances = engine::win_chances(board);
+    for &player in &board.players {
+        let win_chance = win_chances.get(&player).copied().unwrap_or(0.0);
+        panel = panel.push(
+            Row::new()
+                .align_items(Align::Center)
+                .spacing(10)
+                .push(
+                    Text::new(&format!("{:?}", player))
+                        .color(player.color())
+                        .width(20),
+                )
+                .push(ProgressBar::new(engine::progress(board, player)).width(110))
+                .push(Text::new(&format!("{:.0}%", win_chance * 100.0))),
+        );
+    }
+    panel
+}
+
+/// The sides of the seat `side` sits in, or just `side` if it has no seat.
+fn seat_sides(board: &Board, side: SideOfStar) -> Vec<SideOfStar> {
+    match board.seat_of(side) {
+        Some(seat) => seat.sides.iter().copied().collect(),
+        None => vec![side],
+    }
+}
+
+/// The moves of the game in play, a page at a time counting back from the
+/// latest, each of which can be clicked to look at the position after it.
+fn moves_panel<'a>(
+    history: &[Move],
+    page: usize,
+    moves_shown: Option<usize>,
+    view_move_button_states: &'a mut [button::State],
+    earlier_button_state: &'a mut button::State,
+    later_button_state: &'a mut button::State,
+    back_to_live_button_state: &'a mut button::State,
+) -> Column<'a, Message> {
+    let mut panel = Column::new()
+        .width(SIDE_PANEL_WIDTH as u32)
+        .padding(20)
+        .spacing(5)
+        .push(Text::new("Moves").size(30));
+    if history.is_empty() {
+        return panel.push(Text::new("No moves yet.").size(16));
+    }
+
+    let pages = history.len().div_ceil(MOVE_LIST_ROWS);
+    let page = page.min(pages - 1);
+    let end = history.len() - page * MOVE_LIST_ROWS;
+    let start = end.saturating_sub(MOVE_LIST_ROWS);
+    for ((number, played), view_move_button_state) in (start + 1..=end)
+        .zip(&history[start..end])
+        .zip(view_move_button_states.iter_mut())
+    {
+        let view_button = Button::new(view_move_button_state, &number.to_string())
+            .width(50)
+            .on_press(Message::ViewPosition(number));
+        let view_button = if moves_shown == Some(number) {
+            view_button
+        } else {
+            view_button.class(button::Class::Secondary)
+        };
+        panel = panel.push(
+            Row::new()
+                .align_items(Align::Center)
+                .spacing(10)
+                .push(view_button)
+                .push(
+                    Text::new(&played.to_string())
+                        .color(played.player.color())
+                        .size(14),
+                ),
+        );
+    }
+
+    let earlier_button = Button::new(earlier_button_state, "Earlier")
+        .width(100)
+        .class(button::Class::Secondary);
+    let later_button = Button::new(later_button_state, "Later")
+        .width(100)
+        .class(button::Class::Secondary);
+    panel = panel.push(
+        Row::new()
+            .spacing(10)
+            .push(if page + 1 < pages {
+                earlier_button.on_press(Message::EarlierMoves)
+            } else {
+                earlier_button
+            })
+            .push(if page > 0 {
+                later_button.on_press(Message::LaterMoves)
+            } else {
+                later_button
+            }),
+    );
+    let back_to_live_button = Button::new(back_to_live_button_state, "Back to live").width(210);
+    panel.push(match moves_shown {
+        Some(_) => back_to_live_button.on_press(Message::BackToLive),
+        None => back_to_live_button.class(button::Class::Secondary),
+    })
+}
+
+/// Every side's accuracy and its worst moves.
+fn analysis_panel<'a>(
+    analysis: &engine::Analysis,
+    analysis_saved: Option<&str>,
+    save_button_state: &'a mut button::State,
+) -> Column<'a, Message> {
+    let mut panel = Column::new()
+        .width(SIDE_PANEL_WIDTH as u32)
+        .padding(20)
+        .spacing(10)
+        .push(Text::new("Analysis").size(30));
+    for (side, report) in &analysis.sides {
+        panel = panel.push(
+            Row::new()
+                .align_items(Align::Center)
+                .spacing(10)
+                .push(
+                    Text::new(&format!("{:?}", side))
+                        .color(side.color())
+                        .width(20),
+                )
+                .push(Text::new(&format!(
+                    "{:.0}%, {} blunders",
+                    report.accuracy, report.blunders
+                ))),
+        );
+    }
+
+    for review in analysis.blunders().take(LISTED_BLUNDERS) {
+        panel = panel.push(
+            Text::new(&format!(
+                "{}. {} -> {} lost {}",
+                review.number, review.played.start, review.played.end, review.distance_lost
+            ))
+            .color(review.side.color())
+            .size(16),
+        );
+    }
+    if analysis.blunders().count() > LISTED_BLUNDERS {
+        panel = panel.push(Text::new("...").size(16));
+    }
+
+    panel = panel.push(
+        Button::new(save_button_state, "Save report")
+            .class(button::Class::Secondary)
+            .on_press(Message::SaveAnalysis),
+    );
+    if let Some(analysis_saved) = analysis_saved {
+        panel = panel.push(Text::new(analysis_saved).size(16));
+    }
+    panel
+}
+
+/// The indices of the archived `games` with `players_count` players and
+/// `player` among them, newest first.
+fn history_games(
+    games: &[ArchivedGame],
+    player: Option<&str>,
+    players_count: Option<usize>,
+) -> Vec<usize> {
+    (0..games.len())
+        .rev()
+        .filter(|&index| {
+            let game = &games[index];
+            players_count.is_none_or(|count| game.players.len() == count)
+                && player.is_none_or(|name| {
+                    game.players
+                        .iter()
+                        .any(|&side| game.player_name(side) == name)
+                })
+        })
+        .collect()
+}
+
+/// Who won an archived game, or how it was drawn.
+fn game_result(game: &ArchivedGame) -> String {
+    match (game.winners(), game.draw) {
+        (Some(winners), _) => {
+            let names: Vec<String> = winners.iter().map(|&side| game.player_name(side)).collect();
+            format!("Won by {}", names.join(", "))
+        }
+        (None, Some(draw)) => format!("Draw by {}", draw.describe()),
+        (None, None) => String::from("Unfinished"),
+    }
+}
+
+fn players_row<'a, I>(label: &str, players: I) -> Row<'a, Message>
+where
+    I: IntoIterator<Item = Player>,
+{
+    players.into_iter().fold(
+        Row::new()
+            .justify_content(Justify::Center)
+            .align_items(Align::Center)
+            .spacing(10)
+            .push(Text::new(label).size(25)),
+        |row, player| {
+            row.push(
+                Text::new(&format!("{:?}", player))
+                    .color(player.color())
+                    .size(40),
+            )
+        },
+    )
+}
+
 pub fn start_game() -> Result<()> {
     <BoardGame as UserInterface>::run(WindowSettings {
         title: String::from("Chinese Checkers"),
@@ -0,0 +1,176 @@
+//! The sprites and textures drawn in place of the flat meshes, loaded from
+//! `resources/` next to where the game runs, the bundled font and the
+//! sounds. When the sprites and textures can't be loaded the board is drawn
+//! with meshes as before, and when the sounds can't be the game is silent.
+
+use coffee::graphics::{
+    Batch, Color, Font, HorizontalAlignment, Image, Point, Quad, Rectangle, Sprite, Target, Text,
+    VerticalAlignment,
+};
+use coffee::load::{Join, Task};
+
+use std::fmt;
+
+use crate::board::{Board, Player, Spot};
+use crate::sound::SoundBackend;
+
+use super::{ideal_radius, SIDE};
+
+const PIECES_PATH: &str = "resources/pieces.png";
+const BOARD_TEXTURE_PATH: &str = "resources/board.png";
+/// the width and height of each sprite in the spritesheet of the pieces,
+/// which has the empty hole first and then a piece of each side from A to F
+const SPRITE_SIZE: u16 = 64;
+const SPRITES: u16 = 7;
+/// the font of the loading screen and of the text drawn over the board,
+/// bundled into the binary so it's there even when nothing in `resources/`
+/// can be loaded
+pub(super) const FONT: &[u8] = include_bytes!("../../resources/fonts/DejaVuSansMono-Bold.ttf");
+const LABEL_SIZE: f32 = 11.0;
+
+#[derive(Clone, Debug)]
+pub(super) struct Assets {
+    pieces: Image,
+    board: Image,
+}
+
+impl Assets {
+    /// Loads every asset, or says which one couldn't be loaded.
+    pub(super) fn load() -> Task<Result<Self, String>> {
+        (
+            Task::stage("Loading the pieces", load_image(PIECES_PATH)),
+            Task::stage("Loading the board", load_image(BOARD_TEXTURE_PATH)),
+        )
+            .join()
+            .map(|(pieces, board)| {
+                let pieces = pieces?;
+                if pieces.width() < SPRITE_SIZE * SPRITES || pieces.height() < SPRITE_SIZE {
+                    return Err(format!(
+                        "{} is {}x{}, too small for {} sprites of {}x{}",
+                        PIECES_PATH,
+                        pieces.width(),
+                        pieces.height(),
+                        SPRITES,
+                        SPRITE_SIZE,
+                        SPRITE_SIZE
+                    ));
+                }
+                Ok(Self {
+                    pieces,
+                    board: board?,
+                })
+            })
+    }
+
+    /// Draws the wooden board under the holes of `board`, centered on the
+    /// origin like `BoardGame::circle_mesh`.
+    pub(super) fn draw_wood(&self, board: &Board, target: &mut Target<'_>) {
+        let margin = 2.5 * SIDE;
+        let (half_width, half_height) = board.iter().fold(
+            (0.0_f32, 0.0_f32),
+            |(half_width, half_height), (coord, _)| {
+                let center = coord.hexagon_center(SIDE);
+                (
+                    half_width.max(center.x.abs()),
+                    half_height.max(center.y.abs()),
+                )
+            },
+        );
+        let (half_width, half_height) = (half_width + margin, half_height + margin);
+        self.board.draw(
+            Quad {
+                position: Point::new(-half_width, -half_height),
+                size: (2.0 * half_width, 2.0 * half_height),
+                ..Quad::default()
+            },
+            target,
+        );
+    }
+
+    /// Draws a sprite in every hole of `board`, in place of
+    /// `BoardGame::circle_mesh`.
+    pub(super) fn draw_holes(&self, board: &Board, target: &mut Target<'_>) {
+        let radius = ideal_radius(SIDE);
+        let scale = 2.0 * radius / f32::from(SPRITE_SIZE);
+        let mut batch = Batch::new(self.pieces.clone());
+        for (coord, spot) in board.iter() {
+            let center = coord.hexagon_center(SIDE);
+            batch.add(Sprite {
+                source: Rectangle {
+                    x: sprite_index(spot) * SPRITE_SIZE,
+                    y: 0,
+                    width: SPRITE_SIZE,
+                    height: SPRITE_SIZE,
+                },
+                position: Point::new(center.x - radius, center.y - radius),
+                scale: (scale, scale),
+            });
+        }
+        batch.draw(target);
+    }
+}
+
+/// The font of the text drawn over the board.
+pub(super) struct Labels {
+    font: Font,
+}
+
+impl fmt::Debug for Labels {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Labels").finish_non_exhaustive()
+    }
+}
+
+impl Labels {
+    pub(super) fn load() -> Task<Self> {
+        Font::load_from_bytes(FONT).map(|font| Self { font })
+    }
+
+    /// Writes the coordinates of every hole of `board` over it, as moves
+    /// are written down but without the brackets.
+    pub(super) fn draw(&mut self, board: &Board, target: &mut Target<'_>) {
+        for (coord, _) in board.iter() {
+            self.font.add(Text {
+                content: &format!("{},{}", coord.horz, coord.slant),
+                position: coord.hexagon_center(SIDE),
+                size: LABEL_SIZE,
+                color: Color::BLACK,
+                horizontal_alignment: HorizontalAlignment::Center,
+                vertical_alignment: VerticalAlignment::Center,
+                ..Text::default()
+            });
+        }
+        self.font.draw(target);
+    }
+}
+
+/// The backend playing the bundled sounds, or what went wrong opening it.
+pub(super) fn load_sounds() -> Task<Result<Box<dyn SoundBackend>, String>> {
+    Task::succeed(|| {
+        #[cfg(feature = "sound")]
+        let backend = crate::sound::Speakers::load()
+            .map(|speakers| Box::new(speakers) as Box<dyn SoundBackend>);
+        #[cfg(not(feature = "sound"))]
+        let backend = Ok(Box::new(crate::sound::Silent) as Box<dyn SoundBackend>);
+        backend
+    })
+}
+
+fn load_image(path: &'static str) -> Task<Result<Image, String>> {
+    Task::using_gpu(move |gpu| {
+        Ok(Image::new(gpu, path).map_err(|error| format!("couldn't load {}: {}", path, error)))
+    })
+}
+
+/// Which sprite of the spritesheet shows `spot`.
+fn sprite_index(spot: Spot) -> u16 {
+    match spot {
+        Spot::Empty => 0,
+        Spot::Player(Player::A) => 1,
+        Spot::Player(Player::B) => 2,
+        Spot::Player(Player::C) => 3,
+        Spot::Player(Player::D) => 4,
+        Spot::Player(Player::E) => 5,
+        Spot::Player(Player::F) => 6,
+    }
+}
@@ -1,5 +1,5 @@
 use coffee::graphics::Point;
-use coffee::input::{mouse, ButtonState, Event, Input};
+use coffee::input::{keyboard, mouse, ButtonState, Event, Input};
 
 #[derive(Debug, Clone, Default)]
 pub struct DragNDrop {
@@ -7,6 +7,12 @@ pub struct DragNDrop {
     start_pos: Option<Point>,
     current_pos: Option<Point>,
     is_dropped: bool,
+    /// the characters typed since the last frame
+    typed: String,
+    cursor_pos: Option<Point>,
+    /// where the left button was pressed and released without dragging,
+    /// since the last frame
+    clicked: Option<Point>,
 }
 
 impl Input for DragNDrop {
@@ -15,10 +21,13 @@ impl Input for DragNDrop {
     }
 
     fn update(&mut self, event: Event) {
-        // we only care for mouse events
-        if let Event::Mouse(event) = event {
+        // besides typed text, we only care for mouse events
+        if let Event::Keyboard(keyboard::Event::TextEntered { character }) = event {
+            self.typed.push(character);
+        } else if let Event::Mouse(event) = event {
             if let mouse::Event::CursorMoved { x, y } = event {
                 let point = [x, y].into();
+                self.cursor_pos = Some(point);
                 match (
                     self.drag_started,
                     self.start_pos.is_some(),
@@ -40,19 +49,22 @@ impl Input for DragNDrop {
                     // all other combinations are invalid
                     _ => unreachable!(),
                 }
-            } else if let mouse::Event::Input { button, state } = event {
-                // we only care about the left mouse button
-                if let mouse::Button::Left = button {
-                    match state {
-                        ButtonState::Pressed => {
-                            self.drag_started = true;
-                        }
-                        ButtonState::Released => {
-                            if self.current_pos.is_none() {
-                                self.reset()
-                            } else {
-                                self.is_dropped = true;
-                            }
+            // we only care about the left mouse button
+            } else if let mouse::Event::Input {
+                button: mouse::Button::Left,
+                state,
+            } = event
+            {
+                match state {
+                    ButtonState::Pressed => {
+                        self.drag_started = true;
+                    }
+                    ButtonState::Released => {
+                        if self.current_pos.is_none() {
+                            self.reset();
+                            self.clicked = self.cursor_pos;
+                        } else {
+                            self.is_dropped = true;
                         }
                     }
                 }
@@ -61,6 +73,8 @@ impl Input for DragNDrop {
     }
 
     fn clear(&mut self) {
+        self.typed.clear();
+        self.clicked = None;
         if self.is_dropped {
             self.reset();
         }
@@ -68,13 +82,23 @@ impl Input for DragNDrop {
 }
 
 impl DragNDrop {
+    /// Forgets the drag, but not what else happened this frame.
     fn reset(&mut self) {
-        *self = Self::default();
+        self.drag_started = false;
+        self.start_pos = None;
+        self.current_pos = None;
+        self.is_dropped = false;
     }
 
     pub fn is_dropped(&self) -> bool {
         self.is_dropped
     }
+    pub fn clicked(&self) -> Option<Point> {
+        self.clicked
+    }
+    pub fn typed(&self) -> &str {
+        &self.typed
+    }
     pub fn drag_status(&self) -> Option<(Point, Point)> {
         Some((self.current_pos?, self.start_pos?))
     }
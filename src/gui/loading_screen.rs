@@ -0,0 +1,81 @@
+//! The screen shown while the game loads: the title, a hole for every side
+//! filling with its color as the loading goes on, and what is being loaded.
+
+use std::fmt;
+
+use coffee::graphics::{
+    Color, Font, Frame, Gpu, HorizontalAlignment, Mesh, Point, Shape, Text, VerticalAlignment,
+};
+use coffee::load::{LoadingScreen, Progress};
+use coffee::Result;
+
+use crate::board::{SideOfStar, Spot};
+
+use super::assets::FONT;
+
+const HOLE_RADIUS: f32 = 14.0;
+const HOLE_SPACING: f32 = 40.0;
+
+pub(super) struct Splash {
+    font: Font,
+}
+
+impl fmt::Debug for Splash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Splash").finish_non_exhaustive()
+    }
+}
+
+impl LoadingScreen for Splash {
+    fn new(gpu: &mut Gpu) -> Result<Self> {
+        Ok(Self {
+            font: Font::from_bytes(gpu, FONT)?,
+        })
+    }
+
+    fn draw(&mut self, progress: &Progress, frame: &mut Frame<'_>) {
+        frame.clear(Color::BLACK);
+        let center = Point::new(frame.width() / 2.0, frame.height() / 2.0);
+
+        let sides = SideOfStar::all();
+        let filled = (progress.percentage() / 100.0 * sides.len() as f32).floor() as usize;
+        let first_x = center.x - HOLE_SPACING * (sides.len() - 1) as f32 / 2.0;
+        let mut holes = Mesh::new();
+        for (i, side) in sides.into_iter().enumerate() {
+            let color = if i < filled {
+                side.color()
+            } else {
+                Spot::Empty.color()
+            };
+            holes.fill(
+                Shape::Circle {
+                    center: Point::new(first_x + HOLE_SPACING * i as f32, center.y),
+                    radius: HOLE_RADIUS,
+                },
+                color,
+            );
+        }
+        holes.draw(&mut frame.as_target());
+
+        self.font.add(Text {
+            content: "Chinese Checkers",
+            position: Point::new(center.x, center.y - 60.0),
+            size: 50.0,
+            color: Color::WHITE,
+            horizontal_alignment: HorizontalAlignment::Center,
+            vertical_alignment: VerticalAlignment::Bottom,
+            ..Text::default()
+        });
+        if let Some(stage) = progress.stage() {
+            self.font.add(Text {
+                content: stage,
+                position: Point::new(center.x, center.y + 40.0),
+                size: 24.0,
+                color: Color::WHITE,
+                horizontal_alignment: HorizontalAlignment::Center,
+                ..Text::default()
+            });
+        }
+        self.font.draw(&mut frame.as_target());
+    }
+}
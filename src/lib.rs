@@ -0,0 +1,10 @@
+pub mod archive;
+pub mod board;
+pub mod engine;
+pub mod gui;
+pub mod profiles;
+pub mod puzzles;
+pub mod solitaire;
+pub mod sound;
+mod storage;
+pub mod tutorial;
@@ -1,9 +1,20 @@
-mod board;
-mod gui;
+mod cli;
 
+use chinese_checkers::gui::start_game;
+use cli::Command;
 use coffee::Result;
-use gui::start_game;
 
 fn main() -> Result<()> {
-    start_game()
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match Command::parse(&args) {
+        Ok(Command::Play) => return start_game(),
+        Ok(Command::Perft { board, depth }) => cli::perft(&board, depth),
+        Err(usage) => Err(usage),
+    };
+
+    if let Err(message) = result {
+        eprintln!("{}", message);
+        std::process::exit(2);
+    }
+    Ok(())
 }
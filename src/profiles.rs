@@ -0,0 +1,221 @@
+//! Local player profiles with ratings that carry over from game to game,
+//! kept as JSON in a file next to the game.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::f64::consts::{LN_10, PI};
+use std::path::Path;
+use std::{fmt, io};
+
+use serde::{Deserialize, Serialize};
+
+use crate::board::{GameOutcome, Player, SideOfStar};
+use crate::storage::{load_json, save_json};
+
+pub const PROFILES_PATH: &str = "profiles.json";
+pub const MAX_NAME_LENGTH: usize = 20;
+
+const INITIAL_ELO: f64 = 1500.0;
+/// how many points a single head to head result can move an Elo rating
+const ELO_K: f64 = 32.0;
+const INITIAL_DEVIATION: f64 = 350.0;
+/// keeps Glicko ratings of regulars from freezing in place
+const MIN_DEVIATION: f64 = 30.0;
+const GLICKO_Q: f64 = LN_10 / 400.0;
+
+/// A Glicko rating, which also tracks how sure it is of itself.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Glicko {
+    pub rating: f64,
+    /// the rating is likely within twice this of the true strength
+    pub deviation: f64,
+}
+
+impl Default for Glicko {
+    fn default() -> Self {
+        Self {
+            rating: INITIAL_ELO,
+            deviation: INITIAL_DEVIATION,
+        }
+    }
+}
+
+impl Glicko {
+    /// How much a result against someone with this rating counts, less the
+    /// less sure their rating is.
+    fn weight(self) -> f64 {
+        1.0 / (1.0 + 3.0 * (GLICKO_Q * self.deviation / PI).powi(2)).sqrt()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    /// the side whose color they like to play
+    pub color: SideOfStar,
+    pub elo: f64,
+    pub glicko: Glicko,
+    pub games: usize,
+    pub wins: usize,
+}
+
+impl Profile {
+    pub fn new(name: &str, color: SideOfStar) -> Self {
+        Self {
+            name: name.to_string(),
+            color,
+            elo: INITIAL_ELO,
+            glicko: Glicko::default(),
+            games: 0,
+            wins: 0,
+        }
+    }
+}
+
+/// Why a profile can't be added.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProfileError {
+    EmptyName,
+    NameTaken(String),
+}
+
+impl fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyName => write!(f, "type a name first"),
+            Self::NameTaken(name) => write!(f, "there already is a profile called {:?}", name),
+        }
+    }
+}
+
+impl Error for ProfileError {}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Profiles {
+    pub profiles: Vec<Profile>,
+}
+
+impl Profiles {
+    /// The profiles saved at `path`, or none if nothing was saved yet.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        load_json(path)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        save_json(self, path)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Profile> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    /// Adds a new profile, in the next color nobody prefers yet if any.
+    pub fn add(&mut self, name: &str) -> Result<&Profile, ProfileError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(ProfileError::EmptyName);
+        }
+        if self.get(name).is_some() {
+            return Err(ProfileError::NameTaken(name.to_string()));
+        }
+
+        let color = SideOfStar::all()
+            .into_iter()
+            .find(|&side| self.profiles.iter().all(|profile| profile.color != side))
+            .unwrap_or_else(|| SideOfStar::all()[self.profiles.len() % 6]);
+        self.profiles.push(Profile::new(name, color));
+        Ok(self.profiles.last().unwrap())
+    }
+
+    /// The profiles, highest Elo first.
+    pub fn leaderboard(&self) -> Vec<&Profile> {
+        let mut leaderboard: Vec<&Profile> = self.profiles.iter().collect();
+        leaderboard.sort_by(|first, second| second.elo.total_cmp(&first.elo));
+        leaderboard
+    }
+
+    /// Updates the ratings of the profiles playing at `seats` after a game
+    /// ending in `outcome`. Everybody plays everybody else: a better place
+    /// wins and the same place draws. Someone playing several sides takes
+    /// the place of their best one. Returns whether at least two profiles
+    /// played, which a game needs to count.
+    pub fn record_game(
+        &mut self,
+        seats: &BTreeMap<Player, String>,
+        players: &BTreeSet<Player>,
+        outcome: &GameOutcome,
+    ) -> bool {
+        let side_places = outcome.places(players);
+        let mut places: BTreeMap<usize, usize> = BTreeMap::new();
+        for (side, name) in seats {
+            let index = self
+                .profiles
+                .iter()
+                .position(|profile| &profile.name == name);
+            if let (Some(index), Some(&place)) = (index, side_places.get(side)) {
+                let best = places.entry(index).or_insert(place);
+                *best = place.min(*best);
+            }
+        }
+        if places.len() < 2 {
+            return false;
+        }
+
+        let before: Vec<Profile> = self.profiles.clone();
+        let opponents = (places.len() - 1) as f64;
+        for (&index, &place) in &places {
+            let results: Vec<(&Profile, f64)> = places
+                .iter()
+                .filter(|&(&other, _)| other != index)
+                .map(|(&other, &other_place)| {
+                    let score = match place.cmp(&other_place) {
+                        std::cmp::Ordering::Less => 1.0,
+                        std::cmp::Ordering::Equal => 0.5,
+                        std::cmp::Ordering::Greater => 0.0,
+                    };
+                    (&before[other], score)
+                })
+                .collect();
+
+            let own = &before[index];
+            let elo_change: f64 = results
+                .iter()
+                .map(|(other, score)| score - expected_score(own.elo, other.elo, 1.0))
+                .sum();
+            let glicko = glicko_update(own.glicko, &results);
+
+            let profile = &mut self.profiles[index];
+            profile.elo += ELO_K / opponents * elo_change;
+            profile.glicko = glicko;
+            profile.games += 1;
+            if place == 0 && outcome.winning_team().is_some() {
+                profile.wins += 1;
+            }
+        }
+        true
+    }
+}
+
+/// The expected score of a player rated `rating` against one rated
+/// `other_rating`, a win being 1.
+fn expected_score(rating: f64, other_rating: f64, weight: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-weight * (rating - other_rating) / 400.0))
+}
+
+/// Glicko's update of `own` over a rating period with `results`.
+fn glicko_update(own: Glicko, results: &[(&Profile, f64)]) -> Glicko {
+    let mut variance_inverse = 0.0;
+    let mut improvement = 0.0;
+    for (other, score) in results {
+        let weight = other.glicko.weight();
+        let expected = expected_score(own.rating, other.glicko.rating, weight);
+        variance_inverse += GLICKO_Q.powi(2) * weight.powi(2) * expected * (1.0 - expected);
+        improvement += weight * (score - expected);
+    }
+
+    let precision = 1.0 / own.deviation.powi(2) + variance_inverse;
+    Glicko {
+        rating: own.rating + GLICKO_Q / precision * improvement,
+        deviation: (1.0 / precision).sqrt().max(MIN_DEVIATION),
+    }
+}
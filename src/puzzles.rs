@@ -0,0 +1,173 @@
+//! Puzzles: positions with a task for the side to move, checked against
+//! every move the engine finds that does the task. The other sides' pieces
+//! only stand in the way, they never move.
+//!
+//! The puzzles that come with the game live in `puzzles.json` next to this
+//! file; which of them were solved is kept as JSON next to the game.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::board::{Board, HexCoord, ParsePositionError};
+use crate::engine;
+use crate::storage::{load_json, save_json};
+
+pub const PUZZLE_PROGRESS_PATH: &str = "puzzle_progress.json";
+
+const BUNDLED_PUZZLES: &str = include_str!("puzzles.json");
+
+/// What the side to move has to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Goal {
+    /// bring a piece into the goal triangle with a single move
+    EnterGoal,
+    /// play the move chaining together the most jumps
+    LongestJump,
+    /// fill the goal triangle within this many moves
+    FinishIn(usize),
+}
+
+impl fmt::Display for Goal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EnterGoal => write!(f, "Reach the goal triangle in one move"),
+            Self::LongestJump => write!(f, "Find the longest jump chain"),
+            Self::FinishIn(1) => write!(f, "Fill the goal triangle in 1 move"),
+            Self::FinishIn(moves) => write!(f, "Fill the goal triangle in {} moves", moves),
+        }
+    }
+}
+
+impl Goal {
+    /// Every move that does the task on `board`.
+    pub fn solutions(self, board: &Board) -> Vec<(HexCoord, HexCoord)> {
+        match self {
+            Self::EnterGoal => engine::goal_entries(board),
+            Self::LongestJump => engine::longest_jumps(board),
+            Self::FinishIn(moves) => engine::finishing_moves(board, moves),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Puzzle {
+    pub name: String,
+    pub goal: Goal,
+    /// see `Board::to_position_string`
+    pub position: String,
+}
+
+impl Puzzle {
+    /// The puzzles that come with the game, easiest first.
+    pub fn bundled() -> Vec<Self> {
+        serde_json::from_str(BUNDLED_PUZZLES).expect("the bundled puzzles are valid JSON")
+    }
+
+    pub fn board(&self) -> Result<Board, ParsePositionError> {
+        Board::from_position_string(&self.position)
+    }
+}
+
+/// What became of a move tried on a puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Attempted {
+    /// the move isn't allowed at all
+    Illegal,
+    /// the move is allowed but doesn't do the task, so it wasn't played
+    Wrong,
+    /// the move was played and the task goes on
+    Correct,
+    /// the move was played and finished the task
+    Solved,
+}
+
+/// Someone working on a puzzle.
+#[derive(Debug, Clone)]
+pub struct Attempt {
+    pub board: Board,
+    /// what's left of the task
+    pub goal: Goal,
+    pub mistakes: usize,
+    pub solved: bool,
+}
+
+impl Attempt {
+    pub fn new(puzzle: &Puzzle) -> Result<Self, ParsePositionError> {
+        Ok(Self {
+            board: puzzle.board()?,
+            goal: puzzle.goal,
+            mistakes: 0,
+            solved: false,
+        })
+    }
+
+    pub fn solutions(&self) -> Vec<(HexCoord, HexCoord)> {
+        if self.solved {
+            return Vec::new();
+        }
+        self.goal.solutions(&self.board)
+    }
+
+    /// Plays the move if it does what the puzzle asks.
+    pub fn try_move(&mut self, start: HexCoord, end: HexCoord) -> Attempted {
+        if self.solved || !self.board.validate_move(start, end) {
+            return Attempted::Illegal;
+        }
+        if !self.solutions().contains(&(start, end)) {
+            self.mistakes += 1;
+            return Attempted::Wrong;
+        }
+
+        let side = self.board.turn;
+        self.board.make_move(start, end);
+        self.board.turn = side;
+        self.goal = match self.goal {
+            Goal::FinishIn(moves) if !self.board.rules().has_finished(&self.board, side) => {
+                Goal::FinishIn(moves - 1)
+            }
+            goal => {
+                self.solved = true;
+                goal
+            }
+        };
+        if self.solved {
+            Attempted::Solved
+        } else {
+            Attempted::Correct
+        }
+    }
+}
+
+/// Which puzzles were tried and solved, by name.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PuzzleProgress {
+    pub solved: BTreeSet<String>,
+    /// how many times each puzzle was started
+    pub tries: BTreeMap<String, usize>,
+}
+
+impl PuzzleProgress {
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        load_json(path)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        save_json(self, path)
+    }
+
+    pub fn is_solved(&self, puzzle: &Puzzle) -> bool {
+        self.solved.contains(&puzzle.name)
+    }
+
+    pub fn start(&mut self, puzzle: &Puzzle) {
+        *self.tries.entry(puzzle.name.clone()).or_default() += 1;
+    }
+
+    pub fn solve(&mut self, puzzle: &Puzzle) {
+        self.solved.insert(puzzle.name.clone());
+    }
+}
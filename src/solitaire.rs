@@ -0,0 +1,128 @@
+//! Solitaire: a single side crossing the board alone, maybe around a few
+//! fixed obstacles, in as few moves as it can. Every challenge has a par,
+//! the fewest moves crossing takes as far as the engine can prove it, and
+//! the best result for each is kept as JSON next to the game.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
+
+use maplit::btreeset;
+use serde::{Deserialize, Serialize};
+
+use crate::board::{Board, HexCoord, Player, StandardRules};
+use crate::engine::{solve_solitaire, SolitaireSolution};
+use crate::storage::{load_json, save_json};
+
+pub const SOLITAIRE_RECORDS_PATH: &str = "solitaire.json";
+/// how many positions the search for a route keeps after each move
+pub const PAR_SEARCH_WIDTH: usize = 5000;
+/// how many positions the search for the shortest route visits before it
+/// settles for a lower bound, about half a minute's worth
+pub const PAR_SEARCH_NODES: u64 = 20_000_000;
+
+/// Pieces that never move, laid out the same way seen from every side so
+/// every side faces the same challenge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Obstacles {
+    // the records were once kept by name
+    #[serde(alias = "Open board")]
+    None,
+    /// the holes around the center
+    Ring,
+    /// two rings further out, which make for handy jumps
+    #[serde(alias = "Stepping stones")]
+    SteppingStones,
+}
+
+impl Obstacles {
+    pub fn all() -> [Self; 3] {
+        [Self::None, Self::Ring, Self::SteppingStones]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::None => "Open board",
+            Self::Ring => "Ring",
+            Self::SteppingStones => "Stepping stones",
+        }
+    }
+
+    pub fn coords(self) -> Vec<HexCoord> {
+        let coords: &[(i32, i32)] = match self {
+            Self::None => &[],
+            Self::Ring => &[(1, 0), (1, -1), (0, -1), (-1, 0), (-1, 1), (0, 1)],
+            Self::SteppingStones => &[
+                (2, -1),
+                (1, 1),
+                (-1, 2),
+                (-2, 1),
+                (-1, -1),
+                (1, -2),
+                (3, 0),
+                (0, 3),
+                (-3, 3),
+                (-3, 0),
+                (0, -3),
+                (3, -3),
+            ],
+        };
+        coords.iter().map(|&coord| coord.into()).collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Challenge {
+    pub side: Player,
+    pub obstacles: Obstacles,
+}
+
+impl Challenge {
+    /// The side in its home triangle, alone but for the obstacles. These
+    /// are shown in the colors of the next side, which isn't playing.
+    pub fn board(self) -> Board {
+        let mut board = Board::with_players(btreeset![self.side], StandardRules)
+            .expect("a single side is a valid setup");
+        for coord in self.obstacles.coords() {
+            board.put_player(coord, self.side.forward());
+        }
+        board
+    }
+
+    /// The shortest route the engine finds and the fewest moves crossing
+    /// takes, `None` if it finds no way. This takes a while.
+    pub fn par(self) -> Option<SolitaireSolution> {
+        let board = self.board();
+        solve_solitaire(&board, self.side, PAR_SEARCH_WIDTH, PAR_SEARCH_NODES)
+    }
+}
+
+/// The fewest moves anyone crossed in, by the obstacles, which are the same
+/// for every side.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SolitaireRecords {
+    pub best: BTreeMap<Obstacles, usize>,
+}
+
+impl SolitaireRecords {
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        load_json(path)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        save_json(self, path)
+    }
+
+    pub fn best(&self, challenge: Challenge) -> Option<usize> {
+        self.best.get(&challenge.obstacles).copied()
+    }
+
+    /// Keeps `moves` if it beats the best so far, telling whether it did.
+    pub fn record(&mut self, challenge: Challenge, moves: usize) -> bool {
+        if self.best(challenge).is_some_and(|best| best <= moves) {
+            return false;
+        }
+        self.best.insert(challenge.obstacles, moves);
+        true
+    }
+}
@@ -0,0 +1,164 @@
+//! Sound effects: the moments of the game that make a sound, the volume and
+//! mute settings kept as JSON next to the game, and the backend that plays
+//! them. As `coffee` 0.4 has no audio, the bundled sounds are played with
+//! `rodio` when the `sound` feature is on, and the game is silent otherwise.
+
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::board::{Board, Player};
+use crate::storage::{load_json, save_json};
+
+#[cfg(feature = "sound")]
+mod speakers;
+
+#[cfg(feature = "sound")]
+pub use speakers::Speakers;
+
+pub const SOUND_SETTINGS_PATH: &str = "sound.json";
+/// how much the volume changes with each press of a volume button
+pub const VOLUME_STEP: u8 = 10;
+
+/// Something happening in the game that makes a sound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SoundEvent {
+    /// a piece is lifted off the board
+    Pickup,
+    /// a piece steps or jumps once, played for every jump of a chain
+    Hop,
+    /// a piece is dropped where it can't go
+    IllegalDrop,
+    /// another side is to move
+    TurnChange,
+    GameOver,
+}
+
+impl SoundEvent {
+    pub const ALL: [SoundEvent; 5] = [
+        SoundEvent::Pickup,
+        SoundEvent::Hop,
+        SoundEvent::IllegalDrop,
+        SoundEvent::TurnChange,
+        SoundEvent::GameOver,
+    ];
+
+    /// Where the sound of the event is bundled, as a WAV file in
+    /// `resources/` next to where the game runs.
+    pub fn path(self) -> &'static str {
+        match self {
+            SoundEvent::Pickup => "resources/sounds/pickup.wav",
+            SoundEvent::Hop => "resources/sounds/hop.wav",
+            SoundEvent::IllegalDrop => "resources/sounds/illegal_drop.wav",
+            SoundEvent::TurnChange => "resources/sounds/turn_change.wav",
+            SoundEvent::GameOver => "resources/sounds/game_over.wav",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SoundSettings {
+    /// in percent
+    pub volume: u8,
+    pub muted: bool,
+}
+
+impl Default for SoundSettings {
+    fn default() -> Self {
+        Self {
+            volume: 80,
+            muted: false,
+        }
+    }
+}
+
+impl SoundSettings {
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        load_json(path)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        save_json(self, path)
+    }
+
+    /// How loud sounds play, from 0 to 1, which is 0 while muted.
+    pub fn gain(&self) -> f32 {
+        if self.muted {
+            0.0
+        } else {
+            f32::from(self.volume.min(100)) / 100.0
+        }
+    }
+
+    pub fn louder(&mut self) {
+        self.volume = self.volume.saturating_add(VOLUME_STEP).min(100);
+    }
+
+    pub fn quieter(&mut self) {
+        self.volume = self.volume.saturating_sub(VOLUME_STEP);
+    }
+}
+
+/// Plays the sound for an event. Implementations load their sounds up front
+/// so playing never waits on the disk.
+pub trait SoundBackend {
+    /// `gain` is from 0 to 1 and never 0.
+    fn play(&mut self, event: SoundEvent, gain: f32);
+}
+
+/// Plays nothing, for builds without the `sound` feature and machines
+/// without a sound card.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Silent;
+
+impl SoundBackend for Silent {
+    fn play(&mut self, _event: SoundEvent, _gain: f32) {}
+}
+
+/// The settings and the backend sounds are played with.
+pub struct Sounds {
+    pub settings: SoundSettings,
+    backend: Box<dyn SoundBackend>,
+}
+
+impl fmt::Debug for Sounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sounds")
+            .field("settings", &self.settings)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Sounds {
+    pub fn new(settings: SoundSettings, backend: Box<dyn SoundBackend>) -> Self {
+        Self { settings, backend }
+    }
+
+    /// Plays the sounds from now on with `backend`, once it's loaded.
+    pub fn set_backend(&mut self, backend: Box<dyn SoundBackend>) {
+        self.backend = backend;
+    }
+
+    pub fn play(&mut self, event: SoundEvent) {
+        let gain = self.settings.gain();
+        if gain > 0.0 {
+            self.backend.play(event, gain);
+        }
+    }
+
+    /// The sounds of `mover` making a move of `hops` steps or jumps on
+    /// `board`, which is the board after the move: a hop each, then the end
+    /// of the game or the turn passing on.
+    pub fn play_move(&mut self, board: &Board, mover: Player, hops: usize) {
+        for _ in 0..hops {
+            self.play(SoundEvent::Hop);
+        }
+        if board.is_over() {
+            self.play(SoundEvent::GameOver);
+        } else if board.turn != mover {
+            self.play(SoundEvent::TurnChange);
+        }
+    }
+}
@@ -0,0 +1,64 @@
+//! The backend playing the bundled sounds on the default output device,
+//! with `rodio`.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io::Cursor;
+
+use rodio::source::Buffered;
+use rodio::{Decoder, OutputStream, Sink, Source};
+
+use super::{SoundBackend, SoundEvent};
+
+type Sound = Buffered<Decoder<Cursor<Vec<u8>>>>;
+
+/// Plays the sound of every event, one after the other so the hops of a
+/// jump chain are heard one by one.
+pub struct Speakers {
+    /// playing stops once this is dropped
+    _stream: OutputStream,
+    sink: Sink,
+    sounds: HashMap<SoundEvent, Sound>,
+}
+
+impl fmt::Debug for Speakers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Speakers").finish_non_exhaustive()
+    }
+}
+
+impl Speakers {
+    /// Opens the default output device and decodes every bundled sound, or
+    /// says what couldn't be done.
+    pub fn load() -> Result<Self, String> {
+        let mut sounds = HashMap::new();
+        for &event in &SoundEvent::ALL {
+            sounds.insert(event, load_sound(event.path())?);
+        }
+        let (stream, handle) = OutputStream::try_default()
+            .map_err(|error| format!("couldn't open the sound output: {}", error))?;
+        let sink =
+            Sink::try_new(&handle).map_err(|error| format!("couldn't play sounds: {}", error))?;
+        Ok(Self {
+            _stream: stream,
+            sink,
+            sounds,
+        })
+    }
+}
+
+impl SoundBackend for Speakers {
+    fn play(&mut self, event: SoundEvent, gain: f32) {
+        if let Some(sound) = self.sounds.get(&event) {
+            self.sink.append(sound.clone().amplify(gain));
+        }
+    }
+}
+
+fn load_sound(path: &str) -> Result<Sound, String> {
+    let bytes = fs::read(path).map_err(|error| format!("couldn't load {}: {}", path, error))?;
+    let decoder = Decoder::new_wav(Cursor::new(bytes))
+        .map_err(|error| format!("couldn't decode {}: {}", path, error))?;
+    Ok(decoder.buffered())
+}
@@ -0,0 +1,23 @@
+//! Reading and writing the JSON files the game keeps in the directory it
+//! is started from.
+
+use std::path::Path;
+use std::{fs, io};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// The value saved at `path`, or the default if nothing was saved yet.
+pub(crate) fn load_json<T: DeserializeOwned + Default>(path: impl AsRef<Path>) -> io::Result<T> {
+    match fs::read_to_string(path) {
+        Ok(json) => serde_json::from_str(&json)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error)),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(T::default()),
+        Err(error) => Err(error),
+    }
+}
+
+pub(crate) fn save_json<T: Serialize>(value: &T, path: impl AsRef<Path>) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(value).expect("saved values only hold plain data");
+    fs::write(path, json)
+}
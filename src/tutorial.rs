@@ -0,0 +1,172 @@
+//! The lessons of the tutorial, each a scripted position with something to
+//! read and usually one move to make.
+
+use maplit::btreeset;
+
+use crate::board::{Board, HexCoord, Player, SideOfStar, StandardRules};
+
+/// the side the tutorial plays
+pub const STUDENT: Player = SideOfStar::A;
+/// the side standing in the way in the lesson on blocking
+const OPPONENT: Player = SideOfStar::D;
+/// the side of the pieces that are only there to be jumped over
+const BYSTANDER: Player = SideOfStar::F;
+
+#[derive(Debug, Clone)]
+pub struct Lesson {
+    pub title: &'static str,
+    /// what to read before making the move
+    pub text: &'static str,
+    /// what to read once the move is made
+    pub done_text: &'static str,
+    pub board: Board,
+    /// the one move that's accepted, if there's a move to make at all
+    pub task: Option<(HexCoord, HexCoord)>,
+    /// the holes to point out
+    pub highlights: Vec<HexCoord>,
+}
+
+impl Lesson {
+    /// A lesson with a move to make, pointing out the piece and the holes
+    /// it passes through.
+    fn with_task(
+        title: &'static str,
+        text: &'static str,
+        done_text: &'static str,
+        board: Board,
+        (start, end): (HexCoord, HexCoord),
+    ) -> Self {
+        let highlights = board.move_path(start, end);
+        Self {
+            title,
+            text,
+            done_text,
+            board,
+            task: Some((start, end)),
+            highlights,
+        }
+    }
+
+    pub fn accepts(&self, start: HexCoord, end: HexCoord) -> bool {
+        self.task == Some((start, end))
+    }
+}
+
+/// An empty board where `STUDENT` moves, possibly against `others`.
+fn empty_board(others: &[Player]) -> Board {
+    let mut players = btreeset![STUDENT];
+    players.extend(others);
+    let mut board = Board::with_players(players, StandardRules)
+        .expect("the student can play alone or against the opposite side");
+    board.clear_pieces();
+    board.turn = STUDENT;
+    board
+}
+
+fn place(board: &mut Board, side: Player, coords: &[(i32, i32)]) {
+    for &coord in coords {
+        board.put_player(coord.into(), side);
+    }
+}
+
+/// Every lesson, in the order they are taught.
+pub fn lessons() -> Vec<Lesson> {
+    let start = Board::new(2).expect("two players is a valid setup");
+    let goal = start.goal(STUDENT);
+    let board_lesson = Lesson {
+        title: "The board",
+        text: "Every side starts with ten pieces in its home triangle. \
+               You play side A, the red pieces at the top. \
+               To win, move all of them into the triangle on the opposite side, \
+               pointed out at the bottom, before the others fill theirs. \
+               Players take turns moving one piece each.",
+        done_text: "",
+        board: start,
+        task: None,
+        highlights: goal.clone(),
+    };
+
+    let mut board = empty_board(&[]);
+    place(&mut board, STUDENT, &[(0, 0)]);
+    let step_lesson = Lesson::with_task(
+        "Steps",
+        "A piece can step into any of the six holes right next to it. \
+         Drag the piece one step towards the bottom.",
+        "Steps are slow though, one hole at a time.",
+        board,
+        ((0, 0).into(), (0, 1).into()),
+    );
+
+    let mut board = empty_board(&[]);
+    place(&mut board, STUDENT, &[(0, -1)]);
+    place(&mut board, BYSTANDER, &[(0, 0)]);
+    let jump_lesson = Lesson::with_task(
+        "Jumps",
+        "A piece can also jump over a neighboring piece of any side, \
+         landing in the empty hole straight behind it. \
+         Jump over the purple piece.",
+        "Nothing happens to the piece jumped over, it stays where it is.",
+        board,
+        ((0, -1).into(), (0, 1).into()),
+    );
+
+    let mut board = empty_board(&[]);
+    place(&mut board, STUDENT, &[(2, -4)]);
+    place(&mut board, BYSTANDER, &[(2, -3), (1, -1), (0, 1), (-1, 3)]);
+    let chain_lesson = Lesson::with_task(
+        "Chain jumps",
+        "After a jump the same piece may keep on jumping, in any direction, \
+         as long as there's a piece to jump over. \
+         The whole chain counts as one move. \
+         Drop the piece at the end of the highlighted chain.",
+        "Long chains are how games are won: \
+         look for pieces, yours or anyone's, lined up with a hole between them.",
+        board,
+        ((2, -4).into(), (-2, 4).into()),
+    );
+
+    let mut board = empty_board(&[]);
+    let last_hole = HexCoord::new(-3, 6);
+    for &coord in goal.iter().filter(|&&coord| coord != last_hole) {
+        board.put_player(coord, STUDENT);
+    }
+    place(&mut board, STUDENT, &[(-3, 4)]);
+    let mut goal_lesson = Lesson::with_task(
+        "Filling the goal",
+        "Once every hole of the opposite triangle holds one of your pieces, \
+         you have finished. \
+         Jump the last piece in.",
+        "That's a win! \
+         In a game with more sides the others play on for the places after you.",
+        board,
+        ((-3, 4).into(), last_hole),
+    );
+    goal_lesson.highlights.extend(goal);
+
+    let mut board = empty_board(&[OPPONENT]);
+    place(&mut board, STUDENT, &[(0, 2), (1, -1)]);
+    place(&mut board, OPPONENT, &[(0, 3)]);
+    place(&mut board, BYSTANDER, &[(0, 0), (0, -2)]);
+    let mut block_lesson = Lesson::with_task(
+        "Blocking",
+        "Jump chains work for everyone. \
+         The green piece of side D could jump over your piece and carry on \
+         all the way up the board. \
+         Step into the highlighted hole to cut its chain short.",
+        "Now the green piece only gets one jump. \
+         Keep an eye on the chains your opponents could make, \
+         and on the pieces you leave for them to jump over.",
+        board,
+        ((1, -1).into(), (0, -1).into()),
+    );
+    block_lesson.highlights.push((0, 3).into());
+
+    vec![
+        board_lesson,
+        step_lesson,
+        jump_lesson,
+        chain_lesson,
+        goal_lesson,
+        block_lesson,
+    ]
+}
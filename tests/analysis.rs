@@ -0,0 +1,44 @@
+use chinese_checkers::engine::{analyze, Verdict, BLUNDER_DISTANCE};
+
+mod common;
+use common::played_board;
+
+#[test]
+fn every_move_is_reviewed() {
+    let board = played_board(3, 30);
+    let analysis = analyze(&board, 1);
+
+    assert_eq!(analysis.moves.len(), board.history.len());
+    let reviewed: usize = analysis.sides.values().map(|report| report.moves).sum();
+    assert_eq!(reviewed, board.history.len());
+    assert_eq!(
+        analysis.sides.keys().copied().collect::<Vec<_>>(),
+        board.players.iter().copied().collect::<Vec<_>>()
+    );
+
+    for (review, played) in analysis.moves.iter().zip(&board.history) {
+        assert_eq!(&review.played, played);
+        assert!(review.distance_lost >= 0);
+        let expected = match review.distance_lost {
+            _ if review.played == review.best => Verdict::Best,
+            0 => Verdict::Good,
+            lost if lost < BLUNDER_DISTANCE => Verdict::Inaccuracy,
+            _ => Verdict::Blunder,
+        };
+        assert_eq!(review.verdict, expected);
+    }
+}
+
+#[test]
+fn report_covers_every_side() {
+    let board = played_board(2, 20);
+    let analysis = analyze(&board, 1);
+
+    let text = analysis.to_string();
+    let json: serde_json::Value = serde_json::from_str(&analysis.to_json()).unwrap();
+    for player in &board.players {
+        assert!(text.contains(&format!("{:?}: ", player)));
+        assert!(json["sides"][format!("{:?}", player)]["accuracy"].is_number());
+    }
+    assert_eq!(json["moves"].as_array().unwrap().len(), 20);
+}
@@ -0,0 +1,111 @@
+use chinese_checkers::board::{AntiSpoiling, Board, Draw, HexCoord, Player, StandardRules};
+
+fn blocked_goal_board(blocked_goal_counts_as_filled: bool) -> Board {
+    let rules: AntiSpoiling = AntiSpoiling {
+        blocked_goal_counts_as_filled,
+        ..AntiSpoiling::default()
+    };
+    let mut board = Board::with_rules(2, rules).unwrap();
+    board.clear_pieces();
+    board
+}
+
+/// Fills `Player::A`'s goal with one of its own pieces and `blockers` in
+/// the rest of the holes.
+fn fill_goal(board: &mut Board, blockers: &[Player]) {
+    let goal = board.goal(Player::A);
+    board.put_player(goal[0], Player::A);
+    for (&coord, &blocker) in goal[1..].iter().zip(blockers.iter().cycle()) {
+        board.put_player(coord, blocker);
+    }
+}
+
+#[test]
+fn blocked_goals_count_as_filled_only_if_asked() {
+    for &counts in &[true, false] {
+        let mut board = blocked_goal_board(counts);
+        fill_goal(&mut board, &[Player::D]);
+        assert_eq!(board.rules().has_finished(&board, Player::A), counts);
+    }
+}
+
+#[test]
+fn only_the_spoiler_blocks_a_goal() {
+    // a third side passing through the goal doesn't block it
+    let mut board = blocked_goal_board(true);
+    fill_goal(&mut board, &[Player::D, Player::B]);
+    assert!(!board.rules().has_finished(&board, Player::A));
+
+    // an empty hole leaves the goal open until A fills it
+    let mut board = blocked_goal_board(true);
+    fill_goal(&mut board, &[Player::D]);
+    board.remove_player(board.goal(Player::A)[1]);
+    assert!(!board.rules().has_finished(&board, Player::A));
+    board.put_player(board.goal(Player::A)[1], Player::A);
+    assert!(board.rules().has_finished(&board, Player::A));
+}
+
+#[test]
+fn stragglers_must_leave_home() {
+    let rules: AntiSpoiling = AntiSpoiling {
+        evacuate_home_after: Some(1),
+        ..AntiSpoiling::default()
+    };
+    let mut board = Board::with_rules(2, rules).unwrap();
+    let home = Player::A.home();
+
+    let (start, end) = board.legal_moves()[0];
+    board.make_move(start, end);
+    let (start, end) = board.legal_moves()[0];
+    board.make_move(start, end);
+
+    assert_eq!(board.turn, Player::A);
+    let legal_moves = board.legal_moves();
+    assert!(!legal_moves.is_empty());
+    assert!(legal_moves
+        .iter()
+        .all(|(start, end)| home.contains(start) && !home.contains(end)));
+
+    let mut relaxed = board.clone();
+    relaxed.set_rules(StandardRules);
+    assert!(relaxed
+        .legal_moves()
+        .iter()
+        .any(|(start, _end)| !home.contains(start)));
+}
+
+/// A board where `Player::A` and `Player::D` each have a piece on the tip of
+/// their home and every other hole is taken by a side that isn't playing,
+/// except for the holes in `open`.
+fn crowded_board(open: &[HexCoord]) -> Board {
+    let mut board = Board::new(2).unwrap();
+    board.clear_pieces();
+    for (coord, _) in board.iter().collect::<Vec<_>>() {
+        if !open.contains(&coord) {
+            board.put_player(coord, Player::C);
+        }
+    }
+    board.put_player(HexCoord::new(4, -8), Player::A);
+    board.put_player(HexCoord::new(-4, 8), Player::D);
+    board
+}
+
+#[test]
+fn players_without_moves_are_skipped() {
+    let mut board = crowded_board(&[HexCoord::new(3, -7)]);
+    board.turn = Player::A;
+
+    board.start_next_turn();
+    assert_eq!(board.turn, Player::A);
+    assert_eq!(board.outcome.draw, None);
+}
+
+#[test]
+fn nobody_moving_is_a_stalemate() {
+    let mut board = crowded_board(&[]);
+    board.turn = Player::A;
+
+    board.start_next_turn();
+    assert_eq!(board.outcome.draw, Some(Draw::Stalemate));
+    assert!(board.is_over());
+}
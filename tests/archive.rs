@@ -0,0 +1,120 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use chinese_checkers::archive::{player_stats, Archive, ArchivedGame};
+use chinese_checkers::board::{AntiSpoiling, Board, Player, SideOfStar};
+use chinese_checkers::engine::BotKind;
+
+fn finished_game() -> (Board, ArchivedGame) {
+    let rules = AntiSpoiling {
+        blocked_goal_counts_as_filled: true,
+        ..AntiSpoiling::default()
+    };
+    let mut board = Board::with_rules(2, rules).unwrap();
+    board.set_goal(Player::A, SideOfStar::C);
+    board.setup_players().unwrap();
+    let mut bot = BotKind::Greedy.build(1);
+    while !board.is_over() {
+        let (start, end) = bot.choose_move(&board).unwrap();
+        board.make_move(start, end);
+    }
+
+    let profiles: BTreeMap<Player, String> =
+        vec![(Player::A, String::from("Ada"))].into_iter().collect();
+    let game = ArchivedGame::new(&board, None, rules, &profiles, Duration::from_secs(95));
+    (board, game)
+}
+
+#[test]
+fn replays_end_where_the_game_did() {
+    let (board, game) = finished_game();
+
+    assert_eq!(game.moves.len(), board.history.len());
+    assert_eq!(game.jumps.len(), board.history.len());
+    let replayed = game.board_after(game.moves.len()).unwrap();
+    assert!(replayed.iter().eq(board.iter()));
+    assert_eq!(replayed.outcome, board.outcome);
+    assert!(game.start_board().unwrap().history.is_empty());
+}
+
+#[test]
+fn jumps_are_counted_per_move() {
+    let (_board, game) = finished_game();
+
+    for (played, &jumps) in game.moves.iter().zip(&game.jumps) {
+        if played.start.distance(played.end) == 1 {
+            assert_eq!(jumps, 0);
+        } else {
+            assert!(jumps >= 1);
+        }
+    }
+    let longest = game.jumps.iter().max().copied().unwrap();
+    assert_eq!(
+        longest,
+        game.longest_jump(Player::A)
+            .max(game.longest_jump(Player::D))
+    );
+
+    let stats = player_stats(&[game.clone(), game.clone()]);
+    assert_eq!(stats["Ada"].games, 2);
+    assert_eq!(stats["Side D"].games, 2);
+    assert_eq!(
+        stats["Ada"].average_longest_jump(),
+        game.longest_jump(Player::A) as f32
+    );
+}
+
+#[test]
+fn archive_survives_saving() {
+    let (_board, game) = finished_game();
+    let archive = Archive { games: vec![game] };
+    let path = std::env::temp_dir().join(format!("games-{}.json", std::process::id()));
+    archive.save(&path).unwrap();
+    let loaded = Archive::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded, archive);
+}
+
+#[test]
+fn finish_dates_are_in_utc() {
+    let (_board, mut game) = finished_game();
+    game.finished_at = 0;
+    assert_eq!(game.finished_date(), "1970-01-01 00:00");
+    game.finished_at = 1_700_000_000;
+    assert_eq!(game.finished_date(), "2023-11-14 22:13");
+    game.finished_at = 951_782_400;
+    assert_eq!(game.finished_date(), "2000-02-29 00:00");
+}
+
+#[test]
+fn games_from_edited_positions_replay_from_there() {
+    let mut start = Board::new(2).unwrap();
+    let mut bot = BotKind::Greedy.build(1);
+    for _ in 0..10 {
+        let (start_coord, end_coord) = bot.choose_move(&start).unwrap();
+        start.make_move(start_coord, end_coord);
+    }
+    let mut start = Board::from_position_string(&start.to_position_string()).unwrap();
+    start.start_from_position().unwrap();
+    let mut board = start.clone();
+    while !board.is_over() {
+        let (start_coord, end_coord) = bot.choose_move(&board).unwrap();
+        board.make_move(start_coord, end_coord);
+    }
+
+    let game = ArchivedGame::new(
+        &board,
+        Some(&start),
+        AntiSpoiling::default(),
+        &BTreeMap::new(),
+        Duration::from_secs(30),
+    );
+    assert_eq!(game.start_position, Some(start.to_position_string()));
+    assert!(game.start_board().unwrap().iter().eq(start.iter()));
+    let replayed = game.board_after(game.moves.len()).unwrap();
+    assert!(replayed.iter().eq(board.iter()));
+    assert_eq!(replayed.outcome, board.outcome);
+    // they don't count for the stats, like for the ratings
+    assert!(player_stats(&[game]).is_empty());
+}
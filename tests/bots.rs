@@ -0,0 +1,36 @@
+use chinese_checkers::board::Board;
+use chinese_checkers::engine::BotKind;
+
+const BOTS: [BotKind; 4] = [
+    BotKind::Random,
+    BotKind::Greedy,
+    BotKind::Minimax(1),
+    BotKind::Mcts(20),
+];
+
+#[test]
+fn bot_names_round_trip() {
+    for &bot in &BOTS {
+        assert_eq!(bot.to_string().parse::<BotKind>(), Ok(bot));
+    }
+    assert_eq!("minimax".parse::<BotKind>(), Ok(BotKind::Minimax(2)));
+    assert!("minimax:deep".parse::<BotKind>().is_err());
+    assert_eq!("minimax:255".parse::<BotKind>(), Ok(BotKind::Minimax(255)));
+    assert!("minimax:300".parse::<BotKind>().is_err());
+    assert!("minimax:-1".parse::<BotKind>().is_err());
+    assert!("random:3".parse::<BotKind>().is_err());
+    assert!("alphazero".parse::<BotKind>().is_err());
+}
+
+#[test]
+fn bots_play_legal_moves() {
+    for &bot in &BOTS {
+        let mut board = Board::new(3).unwrap();
+        let mut players = bot.build(7);
+        for _ in 0..15 {
+            let (start, end) = players.choose_move(&board).unwrap();
+            assert!(board.legal_moves().contains(&(start, end)), "{}", bot);
+            board.make_move(start, end);
+        }
+    }
+}
@@ -0,0 +1,12 @@
+use chinese_checkers::board::Board;
+
+/// A game of `players_count` players after each played the first of its
+/// legal moves, `moves` moves in all.
+pub fn played_board(players_count: usize, moves: usize) -> Board {
+    let mut board = Board::new(players_count).unwrap();
+    for _ in 0..moves {
+        let (start, end) = board.legal_moves()[0];
+        board.make_move(start, end);
+    }
+    board
+}
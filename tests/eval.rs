@@ -0,0 +1,80 @@
+use chinese_checkers::board::{Board, HexCoord, Player};
+use chinese_checkers::engine::{evaluate_side, progress, win_chances};
+
+mod common;
+use common::played_board;
+
+/// a step forward out of each home, the one mirroring the other
+const A_STEP: (HexCoord, HexCoord) = (HexCoord::new(1, -5), HexCoord::new(1, -4));
+const D_STEP: (HexCoord, HexCoord) = (HexCoord::new(-1, 5), HexCoord::new(-1, 4));
+
+#[test]
+fn stepping_forward_improves_the_evaluation() {
+    let mut board = Board::new(2).unwrap();
+    let before = evaluate_side(&board, Player::A);
+    board.make_move(A_STEP.0, A_STEP.1);
+    let after = evaluate_side(&board, Player::A);
+    assert_eq!(after.distance, before.distance - 1);
+    assert!(after.score > before.score);
+}
+
+#[test]
+fn opposite_sides_are_evaluated_alike() {
+    let mut board = Board::new(2).unwrap();
+    assert_eq!(
+        evaluate_side(&board, Player::A),
+        evaluate_side(&board, Player::D)
+    );
+    board.make_move(A_STEP.0, A_STEP.1);
+    assert_ne!(
+        evaluate_side(&board, Player::A),
+        evaluate_side(&board, Player::D)
+    );
+    board.make_move(D_STEP.0, D_STEP.1);
+    assert_eq!(
+        evaluate_side(&board, Player::A),
+        evaluate_side(&board, Player::D)
+    );
+
+    let board = Board::new(6).unwrap();
+    let first = evaluate_side(&board, Player::A);
+    assert!(board
+        .players
+        .iter()
+        .all(|&player| evaluate_side(&board, player) == first));
+}
+
+#[test]
+fn win_chances_add_up_to_one() {
+    let start = Board::new(2).unwrap();
+    let chances = win_chances(&start);
+    assert!((chances[&Player::A] - 0.5).abs() < 1e-6);
+    assert!((chances[&Player::D] - 0.5).abs() < 1e-6);
+
+    for &players_count in &[2, 3, 4, 6] {
+        let board = played_board(players_count, 24);
+        let chances = win_chances(&board);
+        assert_eq!(chances.len(), players_count);
+        assert!(chances
+            .values()
+            .all(|&chance| (0.0..=1.0).contains(&chance)));
+        let total: f32 = chances.values().sum();
+        assert!((total - 1.0).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn progress_goes_from_zero_to_one() {
+    let mut board = Board::new(2).unwrap();
+    assert_eq!(progress(&board, Player::A), 0.0);
+    board.make_move(A_STEP.0, A_STEP.1);
+    let stepped = progress(&board, Player::A);
+    assert!(stepped > 0.0 && stepped < 1.0);
+    assert_eq!(progress(&board, Player::D), 0.0);
+
+    board.clear_pieces();
+    for coord in board.goal(Player::A) {
+        board.put_player(coord, Player::A);
+    }
+    assert_eq!(progress(&board, Player::A), 1.0);
+}
@@ -0,0 +1,69 @@
+use chinese_checkers::board::{Board, Draw, DrawRules, HexCoord, Spot};
+
+/// Steps the piece at `start` to `end` and back, for the player to move and
+/// the one after them, coming back to the same position.
+fn shuffle(board: &mut Board, moves: [(HexCoord, HexCoord); 2]) {
+    for &(start, end) in &moves {
+        board.make_move(start, end);
+    }
+    for &(start, end) in &moves {
+        board.make_move(end, start);
+    }
+}
+
+#[test]
+fn incremental_hash_matches_rebuilt_board() {
+    let mut board = Board::new(3).unwrap();
+    for _ in 0..30 {
+        let (start, end) = board.legal_moves()[0];
+        board.make_move(start, end);
+    }
+
+    let mut rebuilt = Board::new(3).unwrap();
+    for (coord, spot) in board.iter() {
+        match spot {
+            Spot::Empty => rebuilt.remove_player(coord),
+            Spot::Player(player) => rebuilt.put_player(coord, player),
+        }
+    }
+    rebuilt.turn = board.turn;
+
+    assert_eq!(rebuilt.hash(), board.hash());
+}
+
+#[test]
+fn repeated_positions_are_counted() {
+    let mut board = Board::new(2).unwrap();
+    board.draw_rules.repetitions = Some(3);
+    let start_hash = board.hash();
+    // a step forward for A and for D
+    let moves = [
+        (HexCoord::new(1, -5), HexCoord::new(1, -4)),
+        (HexCoord::new(-1, 5), HexCoord::new(-1, 4)),
+    ];
+
+    assert_eq!(board.repetitions(), 1);
+    shuffle(&mut board, moves);
+    assert_eq!(board.hash(), start_hash);
+    assert_eq!(board.repetitions(), 2);
+    assert_eq!(board.outcome.draw, None);
+    shuffle(&mut board, moves);
+    assert_eq!(board.repetitions(), 3);
+    assert_eq!(board.outcome.draw, Some(Draw::Repetition));
+}
+
+#[test]
+fn repetitions_only_draw_when_asked() {
+    let mut board = Board::new(2).unwrap();
+    assert_eq!(board.draw_rules, DrawRules::default());
+    let moves = [
+        (HexCoord::new(1, -5), HexCoord::new(1, -4)),
+        (HexCoord::new(-1, 5), HexCoord::new(-1, 4)),
+    ];
+    for _ in 0..3 {
+        shuffle(&mut board, moves);
+    }
+    assert_eq!(board.repetitions(), 4);
+    assert_eq!(board.outcome.draw, None);
+    assert!(!board.is_over());
+}
@@ -0,0 +1,56 @@
+use chinese_checkers::board::{Board, HexCoord};
+
+/// perft counts from the starting position, for depths 1 to 4
+const PERFT_COUNTS: [(usize, [u64; 4]); 4] = [
+    (2, [14, 196, 4760, 115600]),
+    (3, [14, 196, 2744, 66640]),
+    (4, [14, 199, 2786, 39601]),
+    (6, [14, 199, 2828, 40189]),
+];
+
+#[test]
+fn perft_matches_known_counts() {
+    for &(players_count, counts) in &PERFT_COUNTS {
+        let board = Board::new(players_count).unwrap();
+        for (depth, &count) in (1..).zip(counts.iter()) {
+            assert_eq!(
+                board.perft(depth),
+                count,
+                "perft({}) with {} players",
+                depth,
+                players_count
+            );
+        }
+    }
+}
+
+#[test]
+fn validate_move_agrees_with_legal_moves() {
+    for &(players_count, _) in &PERFT_COUNTS {
+        let mut board = Board::new(players_count).unwrap();
+        for _ in 0..20 {
+            let legal_moves = board.legal_moves();
+            let holes: Vec<HexCoord> = board.iter().map(|(coord, _spot)| coord).collect();
+            for &start in &holes {
+                for &end in &holes {
+                    assert_eq!(
+                        board.validate_move(start, end),
+                        legal_moves.contains(&(start, end)),
+                        "{:?} -> {:?} with {} players",
+                        start,
+                        end,
+                        players_count
+                    );
+                }
+            }
+
+            let &(start, end) = legal_moves
+                .iter()
+                .max_by_key(|(start, end)| {
+                    (end.slant - start.slant).abs() + (end.horz - start.horz).abs()
+                })
+                .unwrap();
+            board.make_move(start, end);
+        }
+    }
+}
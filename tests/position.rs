@@ -0,0 +1,81 @@
+use chinese_checkers::board::{Board, HexCoord, Player, PositionError, SideOfStar, Spot};
+
+#[test]
+fn start_position_is_valid() {
+    let board = Board::new(3).unwrap();
+    assert_eq!(board.validate_position(), Ok(()));
+}
+
+#[test]
+fn edited_positions_are_checked() {
+    let mut board = Board::new(2).unwrap();
+    let home_piece = Player::A.home()[0];
+    board.remove_player(home_piece);
+    assert_eq!(
+        board.validate_position(),
+        Err(PositionError::PieceCount(Player::A, 9))
+    );
+
+    board.put_player(home_piece, Player::B);
+    assert_eq!(
+        board.validate_position(),
+        Err(PositionError::PieceCount(Player::A, 9))
+    );
+    board.put_player(HexCoord::new(0, 0), Player::A);
+    assert_eq!(
+        board.validate_position(),
+        Err(PositionError::StrayPieces(Player::B))
+    );
+
+    board.put_player(home_piece, Player::A);
+    board.remove_player(HexCoord::new(0, 0));
+    board.turn = SideOfStar::C;
+    assert_eq!(
+        board.validate_position(),
+        Err(PositionError::TurnNotPlaying(Player::C))
+    );
+}
+
+#[test]
+fn finished_sides_are_rejected() {
+    let mut board = Board::new(2).unwrap();
+    board.clear_pieces();
+    for (player, other) in [(Player::A, Player::D), (Player::D, Player::A)] {
+        for coord in other.home() {
+            board.put_player(coord, player);
+        }
+    }
+    assert_eq!(
+        board.validate_position(),
+        Err(PositionError::AlreadyFinished(Player::A))
+    );
+}
+
+#[test]
+fn games_start_from_edited_positions() {
+    let mut board = Board::new(2).unwrap();
+    let start = Player::D.home()[0];
+    for _ in 0..4 {
+        let (start, end) = board.legal_moves()[0];
+        board.make_move(start, end);
+    }
+    board.clear_pieces();
+    for (index, coord) in Player::D.home().into_iter().enumerate() {
+        let player = if index == 0 { Player::A } else { Player::D };
+        board.put_player(coord, player);
+    }
+    for coord in Player::A.home().into_iter().skip(1) {
+        board.put_player(coord, Player::A);
+    }
+    board.put_player(Player::A.home()[0], Player::D);
+    board.turn = Player::D;
+
+    board.start_from_position().unwrap();
+    assert!(board.history.is_empty());
+    assert_eq!(board.repetitions(), 1);
+    assert_eq!(board.get(&start), Some(&Spot::Player(Player::A)));
+    assert!(board
+        .legal_moves()
+        .iter()
+        .all(|&(start, _end)| { board.get(&start) == Some(&Spot::Player(Player::D)) }));
+}
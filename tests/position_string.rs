@@ -0,0 +1,88 @@
+use chinese_checkers::board::{Board, HexCoord, ParsePositionError, Player, Spot};
+
+const TWO_PLAYER_START: &str = "A/AA/AAA/AAAA/13/12/11/10/9/10/11/12/13/DDDD/DDD/DD/D AD A";
+
+#[test]
+fn start_position_string() {
+    let board = Board::new(2).unwrap();
+    assert_eq!(board.to_position_string(), TWO_PLAYER_START);
+}
+
+#[test]
+fn position_strings_round_trip() {
+    for players_count in &[1, 2, 3, 4, 6] {
+        let mut board = Board::new(*players_count).unwrap();
+        for _ in 0..5 {
+            let (start, end) = board.legal_moves()[0];
+            board.make_move(start, end);
+        }
+        let position = board.to_position_string();
+        let parsed = Board::from_position_string(&position).unwrap();
+        assert_eq!(parsed.to_position_string(), position);
+        assert_eq!(parsed.players, board.players);
+        assert_eq!(parsed.turn, board.turn);
+        assert_eq!(parsed.hash(), board.hash());
+        assert_eq!(parsed.validate_position(), Ok(()));
+    }
+}
+
+#[test]
+fn parsed_positions_can_be_played() {
+    let mut board = Board::from_position_string(TWO_PLAYER_START).unwrap();
+    assert_eq!(board.perft(1), Board::new(2).unwrap().perft(1));
+    let (start, end) = board.legal_moves()[0];
+    board.make_move(start, end);
+    assert_eq!(board.turn, Player::D);
+    assert_eq!(board.get(&HexCoord::new(0, 0)), Some(&Spot::Empty));
+}
+
+#[test]
+fn bad_position_strings_are_explained() {
+    let parse = |position: &str| Board::from_position_string(position).err().unwrap();
+    assert_eq!(parse("A/AA AD"), ParsePositionError::FieldCount(2));
+    assert_eq!(parse("A/AA/AAA AD A"), ParsePositionError::RowCount(3));
+    assert_eq!(
+        parse("A/AA/AAA/AAAA/13/12/11/10/9/10/11/12/12/DDDD/DDD/DD/D AD A"),
+        ParsePositionError::RowLength {
+            row: 13,
+            expected: 13,
+            found: 12
+        }
+    );
+    assert_eq!(
+        parse("A/AA/AAA/AAAA/14/12/11/10/9/10/11/12/13/DDDD/DDD/DD/D AD A"),
+        ParsePositionError::RowLength {
+            row: 5,
+            expected: 13,
+            found: 14
+        }
+    );
+    // a run too long to count in a usize is just as much too long
+    assert_eq!(
+        parse("A/AA/AAA/AAAA/99999999999999999999999/12/11/10/9/10/11/12/13/DDDD/DDD/DD/D AD A"),
+        ParsePositionError::RowLength {
+            row: 5,
+            expected: 13,
+            found: 99
+        }
+    );
+    assert_eq!(
+        parse("A/AA/AAA/AAAA/13/12/11/10/9/10/11/12/13/DDxD/DDD/DD/D AD A"),
+        ParsePositionError::UnknownSymbol {
+            row: 14,
+            symbol: 'x'
+        }
+    );
+    assert_eq!(
+        parse("A/AA/AAA/AAAA/13/12/11/10/9/10/11/12/13/DDDD/DDD/DD/D AG A"),
+        ParsePositionError::UnknownSide('G')
+    );
+    assert_eq!(
+        parse("A/AA/AAA/AAAA/13/12/11/10/9/10/11/12/13/DDDD/DDD/DD/D ADA A"),
+        ParsePositionError::DuplicateSide(Player::A)
+    );
+    assert_eq!(
+        parse("A/AA/AAA/AAAA/13/12/11/10/9/10/11/12/13/DDDD/DDD/DD/D AD B"),
+        ParsePositionError::Turn("B".to_string())
+    );
+}
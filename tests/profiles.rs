@@ -0,0 +1,78 @@
+use std::collections::BTreeMap;
+
+use chinese_checkers::board::{Board, Player, SideOfStar};
+use chinese_checkers::profiles::{ProfileError, Profiles};
+
+fn profiles(names: &[&str]) -> Profiles {
+    let mut profiles = Profiles::default();
+    for name in names {
+        profiles.add(name).unwrap();
+    }
+    profiles
+}
+
+fn seats(pairs: &[(Player, &str)]) -> BTreeMap<Player, String> {
+    pairs
+        .iter()
+        .map(|&(side, name)| (side, name.to_string()))
+        .collect()
+}
+
+#[test]
+fn names_must_be_new_and_not_blank() {
+    let mut profiles = profiles(&["Ada"]);
+    assert_eq!(profiles.add("  "), Err(ProfileError::EmptyName));
+    assert_eq!(
+        profiles.add("Ada "),
+        Err(ProfileError::NameTaken(String::from("Ada")))
+    );
+    assert_ne!(profiles.add("Grace").unwrap().color, SideOfStar::A);
+}
+
+#[test]
+fn winners_gain_what_losers_lose() {
+    let mut board = Board::new(3).unwrap();
+    board.outcome.ranking = vec![Player::C];
+    board.outcome.team_ranking = vec![[Player::C].iter().copied().collect()];
+    let mut profiles = profiles(&["Ada", "Grace", "Alan"]);
+    let seats = seats(&[
+        (Player::A, "Ada"),
+        (Player::C, "Grace"),
+        (Player::E, "Alan"),
+    ]);
+
+    assert!(profiles.record_game(&seats, &board.players, &board.outcome));
+
+    let grace = profiles.get("Grace").unwrap();
+    let ada = profiles.get("Ada").unwrap();
+    assert_eq!((grace.games, grace.wins), (1, 1));
+    assert_eq!((ada.games, ada.wins), (1, 0));
+    assert!(grace.elo > 1500.0 && grace.glicko.rating > 1500.0);
+    assert!(ada.elo < 1500.0 && ada.glicko.rating < 1500.0);
+    assert!(grace.glicko.deviation < 350.0);
+    let total_elo: f64 = profiles.profiles.iter().map(|profile| profile.elo).sum();
+    assert!((total_elo - 3.0 * 1500.0).abs() < 1e-9);
+    assert_eq!(profiles.leaderboard()[0].name, "Grace");
+}
+
+#[test]
+fn games_need_two_profiles() {
+    let board = Board::new(2).unwrap();
+    let mut profiles = profiles(&["Ada"]);
+    let seats = seats(&[(Player::A, "Ada"), (Player::D, "Nobody")]);
+
+    assert!(!profiles.record_game(&seats, &board.players, &board.outcome));
+    assert_eq!(profiles.get("Ada").unwrap().games, 0);
+}
+
+#[test]
+fn profiles_survive_saving() {
+    let path = std::env::temp_dir().join(format!("profiles-{}.json", std::process::id()));
+    let profiles = profiles(&["Ada", "Grace"]);
+    profiles.save(&path).unwrap();
+    let loaded = Profiles::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded, profiles);
+    assert_eq!(Profiles::load(&path).unwrap(), Profiles::default());
+}
@@ -0,0 +1,89 @@
+use chinese_checkers::board::Player;
+use chinese_checkers::engine::can_finish_within;
+use chinese_checkers::puzzles::{Attempt, Attempted, Goal, Puzzle, PuzzleProgress};
+
+#[test]
+fn bundled_puzzles_have_a_few_answers() {
+    let puzzles = Puzzle::bundled();
+    assert!(puzzles.len() >= 8);
+    for puzzle in &puzzles {
+        let board = puzzle.board().unwrap();
+        let solutions = puzzle.goal.solutions(&board);
+        assert!(!solutions.is_empty(), "{} has no answer", puzzle.name);
+        assert!(
+            solutions.len() * 10 < board.legal_moves().len(),
+            "{} is too easy",
+            puzzle.name
+        );
+    }
+}
+
+#[test]
+fn finishing_puzzles_need_every_move() {
+    for puzzle in Puzzle::bundled() {
+        if let Goal::FinishIn(moves) = puzzle.goal {
+            let board = puzzle.board().unwrap();
+            assert!(can_finish_within(&board, board.turn, moves));
+            assert!(!can_finish_within(&board, board.turn, moves - 1));
+        }
+    }
+}
+
+#[test]
+fn playing_the_answers_solves_every_puzzle() {
+    for puzzle in Puzzle::bundled() {
+        let mut attempt = Attempt::new(&puzzle).unwrap();
+        let mut result = Attempted::Correct;
+        while result == Attempted::Correct {
+            let (start, end) = attempt.solutions()[0];
+            result = attempt.try_move(start, end);
+        }
+        assert_eq!(result, Attempted::Solved, "{}", puzzle.name);
+        assert!(attempt.solved);
+        assert_eq!(attempt.mistakes, 0);
+        if let Goal::FinishIn(_) = puzzle.goal {
+            assert!(attempt
+                .board
+                .rules()
+                .has_finished(&attempt.board, Player::A));
+        }
+    }
+}
+
+#[test]
+fn wrong_moves_are_taken_back() {
+    let puzzle = &Puzzle::bundled()[0];
+    let mut attempt = Attempt::new(puzzle).unwrap();
+    let solutions = attempt.solutions();
+    let position = attempt.board.to_position_string();
+    let wrong = attempt
+        .board
+        .legal_moves()
+        .into_iter()
+        .find(|played| !solutions.contains(played))
+        .unwrap();
+
+    assert_eq!(attempt.try_move(wrong.0, wrong.1), Attempted::Wrong);
+    assert_eq!(attempt.try_move(wrong.0, wrong.0), Attempted::Illegal);
+    assert_eq!(attempt.mistakes, 1);
+    assert_eq!(attempt.board.to_position_string(), position);
+    assert!(!attempt.solved);
+}
+
+#[test]
+fn progress_survives_saving() {
+    let path = std::env::temp_dir().join(format!("puzzle-progress-{}.json", std::process::id()));
+    let puzzles = Puzzle::bundled();
+    let mut progress = PuzzleProgress::default();
+    progress.start(&puzzles[0]);
+    progress.start(&puzzles[0]);
+    progress.solve(&puzzles[0]);
+    progress.save(&path).unwrap();
+    let loaded = PuzzleProgress::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded, progress);
+    assert!(loaded.is_solved(&puzzles[0]));
+    assert!(!loaded.is_solved(&puzzles[1]));
+    assert_eq!(loaded.tries[&puzzles[0].name], 2);
+}
@@ -0,0 +1,104 @@
+use std::collections::BTreeSet;
+
+use maplit::btreeset;
+
+use chinese_checkers::board::{
+    AntiSpoiling, Board, HexCoord, Player, RuleSet, SetupError, Spot, StandardRules,
+};
+use chinese_checkers::engine::BotKind;
+
+/// Plays a whole 2 player game between greedy bots.
+fn play_out(mut board: Board) -> Board {
+    let mut bot = BotKind::Greedy.build(1);
+    while !board.is_over() {
+        let (start, end) = bot.choose_move(&board).unwrap();
+        board.make_move(start, end);
+    }
+    board
+}
+
+#[test]
+fn standard_rules_play_a_classic_game() {
+    let mut board = Board::with_rules(2, StandardRules).unwrap();
+    assert_eq!(board.players, btreeset![Player::A, Player::D]);
+    assert_eq!(board.turn, Player::A);
+
+    // only the side to move may move, and only into empty holes
+    let (start, end) = (HexCoord::new(1, -5), HexCoord::new(1, -4));
+    assert!(!board.validate_move(HexCoord::new(-1, 5), HexCoord::new(-1, 4)));
+    assert!(!board.validate_move(start, HexCoord::new(2, -5)));
+    assert!(board.validate_move(start, end));
+    board.make_move(start, end);
+    assert_eq!(board.turn, Player::D);
+    assert_eq!(board.history.len(), 1);
+
+    let board = play_out(board);
+    let winner = board.outcome.winner().unwrap();
+    assert!(board
+        .goal(winner)
+        .iter()
+        .all(|coord| board.get(coord) == Some(&Spot::Player(winner))));
+    assert_eq!(board.outcome.ranking.len(), 2);
+}
+
+#[test]
+fn anti_spoiling_without_options_plays_like_its_inner_rules() {
+    let standard = play_out(Board::with_rules(2, StandardRules).unwrap());
+
+    let rules: AntiSpoiling = AntiSpoiling::default();
+    let mut wrapped = Board::with_rules(2, rules).unwrap();
+    let mut reference = Board::with_rules(2, StandardRules).unwrap();
+    for played in &standard.history {
+        assert_eq!(wrapped.turn, reference.turn);
+        assert_eq!(wrapped.legal_moves(), reference.legal_moves());
+        wrapped.make_move(played.start, played.end);
+        reference.make_move(played.start, played.end);
+    }
+    assert_eq!(wrapped.history, standard.history);
+    assert_eq!(wrapped.outcome, standard.outcome);
+}
+
+/// Two sides across from each other that may only step, never jump.
+#[derive(Debug, Clone, Copy)]
+struct StepsOnly;
+
+impl RuleSet for StepsOnly {
+    fn players(&self, players_count: usize) -> Result<BTreeSet<Player>, SetupError> {
+        match players_count {
+            2 => Ok(btreeset![Player::B, Player::E]),
+            _ => Err(SetupError::UnsupportedPlayerCount(players_count)),
+        }
+    }
+
+    fn is_legal_move(&self, board: &Board, start_coord: HexCoord, end_coord: HexCoord) -> bool {
+        StandardRules.is_legal_move(board, start_coord, end_coord)
+            && start_coord.distance(end_coord) == 1
+    }
+}
+
+#[test]
+fn anti_spoiling_passes_calls_through_to_its_inner_rules() {
+    let rules = AntiSpoiling {
+        inner: StepsOnly,
+        blocked_goal_counts_as_filled: false,
+        evacuate_home_after: None,
+    };
+    assert_eq!(
+        Board::with_rules(3, rules).err(),
+        Some(SetupError::UnsupportedPlayerCount(3))
+    );
+
+    let mut board = Board::with_rules(2, rules).unwrap();
+    assert_eq!(board.players, btreeset![Player::B, Player::E]);
+    assert_eq!(board.turn, Player::B);
+    for _ in 0..10 {
+        let legal_moves = board.legal_moves();
+        assert!(!legal_moves.is_empty());
+        assert!(legal_moves
+            .iter()
+            .all(|&(start, end)| start.distance(end) == 1));
+        let (start, end) = legal_moves[0];
+        board.make_move(start, end);
+    }
+    assert_eq!(board.history.len(), 10);
+}
@@ -0,0 +1,42 @@
+use chinese_checkers::engine::{relative_score, Search};
+
+mod common;
+use common::played_board;
+
+#[test]
+fn one_move_search_picks_the_best_scoring_move() {
+    let board = played_board(2, 12);
+    let player = board.turn;
+    let best_score = board
+        .legal_moves()
+        .into_iter()
+        .map(|(start, end)| {
+            let mut child = board.clone();
+            child.make_move(start, end);
+            relative_score(&child, player)
+        })
+        .max()
+        .unwrap();
+
+    let (start, end) = Search::new(1).best_move(&board).unwrap();
+    let mut child = board.clone();
+    child.make_move(start, end);
+    assert_eq!(relative_score(&child, player), best_score);
+}
+
+#[test]
+fn best_move_is_legal_and_has_a_path() {
+    for &players_count in &[2, 3, 6] {
+        let board = played_board(players_count, 20);
+        let (start, end) = Search::new(2).best_move(&board).unwrap();
+        assert!(board.legal_moves().contains(&(start, end)));
+
+        let path = board.move_path(start, end);
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&end));
+        for hop in path.windows(2) {
+            let length = hop[0].distance(hop[1]);
+            assert!(length == 1 && path.len() == 2 || length == 2);
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use maplit::btreeset;
+
+use chinese_checkers::board::{gen_seats, Board, Player, SideOfStar, TurnOrder};
+
+fn seated_board(seats_count: usize, sides_per_seat: usize, turn_order: TurnOrder) -> Board {
+    let mut board = Board::new(2).unwrap();
+    board.set_seats(gen_seats(seats_count, sides_per_seat).unwrap());
+    board.turn_order = turn_order;
+    board.setup_players().unwrap();
+    board
+}
+
+/// Whose turn it is for the next `moves` moves.
+fn turns(board: &mut Board, moves: usize) -> Vec<Player> {
+    let mut turns = Vec::new();
+    for _ in 0..moves {
+        turns.push(board.turn);
+        let (start, end) = board.legal_moves()[0];
+        board.make_move(start, end);
+    }
+    turns
+}
+
+#[test]
+fn seats_can_own_several_sides() {
+    use SideOfStar::*;
+    for &((seats_count, sides_per_seat), ref seats) in &[
+        ((2, 2), vec![btreeset![A, B], btreeset![D, E]]),
+        ((2, 3), vec![btreeset![A, C, E], btreeset![B, D, F]]),
+        (
+            (3, 2),
+            vec![btreeset![A, B], btreeset![C, D], btreeset![E, F]],
+        ),
+    ] {
+        let board = seated_board(seats_count, sides_per_seat, TurnOrder::PerSide);
+        let sides: Vec<_> = board.seats.iter().map(|seat| seat.sides.clone()).collect();
+        assert_eq!(&sides, seats);
+        assert_eq!(&board.teams, seats);
+        assert_eq!(board.players.len(), seats_count * sides_per_seat);
+        for seat in &board.seats {
+            for &side in &seat.sides {
+                assert_eq!(board.seat_of(side), Some(seat));
+            }
+        }
+    }
+    assert!(gen_seats(4, 2).is_none());
+    assert!(gen_seats(2, 4).is_none());
+}
+
+#[test]
+fn every_side_takes_a_turn_per_side() {
+    use SideOfStar::*;
+    let mut board = seated_board(2, 2, TurnOrder::PerSide);
+    assert_eq!(turns(&mut board, 8), vec![A, B, D, E, A, B, D, E]);
+}
+
+#[test]
+fn every_seat_takes_a_turn_per_seat() {
+    use SideOfStar::*;
+    let mut board = seated_board(3, 2, TurnOrder::PerSeat);
+    assert_eq!(board.movable_players(), vec![A, B]);
+    assert_eq!(turns(&mut board, 6), vec![A, C, E, A, C, E]);
+
+    // a seat may move a piece of any of its sides on its turn
+    let mut board = seated_board(2, 3, TurnOrder::PerSeat);
+    let b_moves = board
+        .legal_moves()
+        .into_iter()
+        .filter(|(start, _end)| Player::B.home().contains(start))
+        .count();
+    assert_eq!(b_moves, 0);
+    let c_move = board
+        .legal_moves()
+        .into_iter()
+        .find(|(start, _end)| Player::C.home().contains(start))
+        .unwrap();
+    board.make_move(c_move.0, c_move.1);
+    assert_eq!(board.turn, Player::B);
+}
@@ -0,0 +1,68 @@
+use maplit::btreeset;
+
+use chinese_checkers::board::{Board, Player, SetupError, SideOfStar, StandardRules};
+
+#[test]
+fn a_game_needs_players() {
+    assert_eq!(Board::new(0).err(), Some(SetupError::NoPlayers));
+    assert_eq!(
+        Board::with_players(btreeset![], StandardRules).err(),
+        Some(SetupError::NoPlayers)
+    );
+    assert_eq!(
+        Board::new(7).err(),
+        Some(SetupError::UnsupportedPlayerCount(7))
+    );
+}
+
+#[test]
+fn goals_cant_be_home() {
+    let mut board = Board::new(2).unwrap();
+    board.set_goal(Player::A, SideOfStar::A);
+    assert_eq!(
+        board.validate_setup(),
+        Err(SetupError::GoalIsHome(Player::A))
+    );
+    assert_eq!(
+        board.setup_players(),
+        Err(SetupError::GoalIsHome(Player::A))
+    );
+}
+
+#[test]
+fn goals_cant_be_shared() {
+    let mut board = Board::new(2).unwrap();
+    board.set_goal(Player::A, SideOfStar::C);
+    board.set_goal(Player::D, SideOfStar::C);
+    assert_eq!(
+        board.validate_setup(),
+        Err(SetupError::SharedGoal(Player::A, Player::D, SideOfStar::C))
+    );
+}
+
+#[test]
+fn team_play_needs_teams() {
+    let mut board = Board::new(3).unwrap();
+    board.team_play = true;
+    assert_eq!(board.validate_setup(), Err(SetupError::NoTeams));
+
+    let mut board = Board::new(4).unwrap();
+    board.team_play = true;
+    assert_eq!(board.validate_setup(), Ok(()));
+}
+
+#[test]
+fn any_sides_can_race_for_custom_goals() {
+    let mut board = Board::with_players(btreeset![Player::A, Player::B], StandardRules).unwrap();
+    board.set_goal(Player::A, SideOfStar::C);
+    board.set_goal(Player::B, SideOfStar::F);
+    assert_eq!(board.setup_players(), Ok(()));
+
+    assert_eq!(board.goal(Player::A), SideOfStar::C.home());
+    assert_eq!(board.goal(Player::B), SideOfStar::F.home());
+    assert_eq!(board.turn, Player::A);
+    for side in SideOfStar::all() {
+        let expected = if board.players.contains(&side) { 10 } else { 0 };
+        assert_eq!(board.pieces(side).len(), expected);
+    }
+}
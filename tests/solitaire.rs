@@ -0,0 +1,127 @@
+use chinese_checkers::board::{Board, HexCoord, Player};
+use chinese_checkers::engine::{solitaire_route, solve_solitaire};
+use chinese_checkers::solitaire::{Challenge, Obstacles, SolitaireRecords};
+
+/// narrow enough to keep the tests quick
+const WIDTH: usize = 50;
+
+#[test]
+fn routes_cross_the_board() {
+    for &obstacles in &Obstacles::all() {
+        let challenge = Challenge {
+            side: Player::C,
+            obstacles,
+        };
+        let mut board = challenge.board();
+        let route = solitaire_route(&board, Player::C, WIDTH).unwrap();
+        for &(start, end) in &route.moves {
+            assert!(board.validate_move(start, end));
+            board.make_move(start, end);
+        }
+        assert!(board.is_over());
+        assert_eq!(board.outcome.winner(), Some(Player::C));
+        assert!(obstacles
+            .coords()
+            .iter()
+            .all(|coord| board.get(coord).unwrap().is_full()));
+    }
+}
+
+#[test]
+fn wider_searches_find_shorter_routes() {
+    let board = Board::new(1).unwrap();
+    let narrow = solitaire_route(&board, Player::A, 10).unwrap();
+    let wide = solitaire_route(&board, Player::A, 200).unwrap();
+    assert!(wide.moves.len() < narrow.moves.len());
+}
+
+#[test]
+fn blocked_goals_cant_be_filled() {
+    let mut board = Board::new(1).unwrap();
+    board.put_player(HexCoord::new(-4, 8), Player::B);
+    assert_eq!(solitaire_route(&board, Player::A, WIDTH), None);
+}
+
+/// `side`'s goal filled but for the `count` pieces nearest the center, each
+/// `steps` short of it.
+fn short_of_goal(side: Player, count: usize, steps: i32) -> Board {
+    let mut board = Board::new(1).unwrap();
+    board.clear_pieces();
+    let mut goal = board.goal(side);
+    goal.sort_by_key(|coord| coord.distance(HexCoord::default()));
+    for &coord in &goal[count..] {
+        board.put_player(coord, side);
+    }
+    for &coord in &goal[..count] {
+        let short = HexCoord::new(coord.horz, coord.slant - steps);
+        assert!(!goal.contains(&short));
+        board.put_player(short, side);
+    }
+    board
+}
+
+#[test]
+fn short_crossings_are_solved() {
+    let mut board = short_of_goal(Player::A, 2, 1);
+    let solution = solve_solitaire(&board, Player::A, WIDTH, 10_000).unwrap();
+    // both pieces have to move
+    assert_eq!(solution.lower_bound, 2);
+    assert_eq!(solution.route.moves.len(), 2);
+    assert!(solution.is_optimal());
+    for &(start, end) in &solution.route.moves {
+        assert!(board.validate_move(start, end));
+        board.make_move(start, end);
+    }
+    assert_eq!(board.outcome.winner(), Some(Player::A));
+}
+
+#[test]
+fn lower_bounds_hold() {
+    let board = short_of_goal(Player::A, 4, 3);
+    let route = solitaire_route(&board, Player::A, WIDTH).unwrap();
+    let solution = solve_solitaire(&board, Player::A, WIDTH, 1_000).unwrap();
+    assert!(solution.route.moves.len() <= route.moves.len());
+    // every piece has to move
+    assert!(solution.lower_bound >= 4);
+    assert!(solution.lower_bound <= solution.route.moves.len());
+}
+
+#[test]
+fn records_keep_the_best() {
+    let challenge = Challenge {
+        side: Player::A,
+        obstacles: Obstacles::Ring,
+    };
+    let same_from_elsewhere = Challenge {
+        side: Player::E,
+        ..challenge
+    };
+    let mut records = SolitaireRecords::default();
+    assert!(records.record(challenge, 40));
+    assert!(!records.record(same_from_elsewhere, 45));
+    assert!(records.record(same_from_elsewhere, 35));
+    assert_eq!(records.best(challenge), Some(35));
+    assert_eq!(
+        records.best(Challenge {
+            obstacles: Obstacles::None,
+            ..challenge
+        }),
+        None
+    );
+
+    let path = std::env::temp_dir().join(format!("solitaire-{}.json", std::process::id()));
+    records.save(&path).unwrap();
+    let loaded = SolitaireRecords::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded, records);
+}
+
+#[test]
+fn records_kept_by_name_still_load() {
+    let path = std::env::temp_dir().join(format!("old-solitaire-{}.json", std::process::id()));
+    std::fs::write(&path, r#"{"best":{"Open board":30,"Stepping stones":22}}"#).unwrap();
+    let loaded = SolitaireRecords::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.best.get(&Obstacles::None), Some(&30));
+    assert_eq!(loaded.best.get(&Obstacles::SteppingStones), Some(&22));
+}
@@ -0,0 +1,84 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use chinese_checkers::board::{Board, HexCoord, Player};
+use chinese_checkers::sound::{SoundBackend, SoundEvent, SoundSettings, Sounds};
+
+/// Keeps what was played, and how loud.
+#[derive(Debug, Clone, Default)]
+struct Recorder(Rc<RefCell<Vec<(SoundEvent, f32)>>>);
+
+impl SoundBackend for Recorder {
+    fn play(&mut self, event: SoundEvent, gain: f32) {
+        self.0.borrow_mut().push((event, gain));
+    }
+}
+
+#[test]
+fn volume_stays_in_range() {
+    let mut settings = SoundSettings::default();
+    for _ in 0..20 {
+        settings.louder();
+    }
+    assert_eq!(settings.volume, 100);
+    assert_eq!(settings.gain(), 1.0);
+    for _ in 0..20 {
+        settings.quieter();
+    }
+    assert_eq!(settings.volume, 0);
+    assert_eq!(settings.gain(), 0.0);
+
+    settings.louder();
+    settings.muted = true;
+    assert_eq!(settings.gain(), 0.0);
+}
+
+#[test]
+fn moves_hop_then_pass_the_turn() {
+    let recorder = Recorder::default();
+    let mut sounds = Sounds::new(SoundSettings::default(), Box::new(recorder.clone()));
+    let mut board = Board::new(2).unwrap();
+
+    // a step is a single hop
+    let (start, end) = (HexCoord::new(1, -5), HexCoord::new(1, -4));
+    let hops = board.move_path(start, end).len() - 1;
+    board.make_move(start, end);
+    sounds.play_move(&board, Player::A, hops);
+    let events: Vec<_> = recorder
+        .0
+        .borrow()
+        .iter()
+        .map(|&(event, _)| event)
+        .collect();
+    assert_eq!(events, vec![SoundEvent::Hop, SoundEvent::TurnChange]);
+    assert!(recorder.0.borrow().iter().all(|&(_, gain)| gain == 0.8));
+
+    // nothing plays while muted
+    recorder.0.borrow_mut().clear();
+    sounds.settings.muted = true;
+    sounds.play(SoundEvent::Pickup);
+    sounds.play_move(&board, Player::D, 3);
+    assert!(recorder.0.borrow().is_empty());
+}
+
+#[test]
+fn settings_are_saved() {
+    let settings = SoundSettings {
+        volume: 30,
+        muted: true,
+    };
+    let path = std::env::temp_dir().join(format!("sound-{}.json", std::process::id()));
+    settings.save(&path).unwrap();
+    let loaded = SoundSettings::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded, settings);
+}
+
+#[test]
+fn every_event_has_a_bundled_sound() {
+    for &event in &SoundEvent::ALL {
+        let bytes = std::fs::read(event.path()).unwrap();
+        assert_eq!(&bytes[..4], b"RIFF", "{}", event.path());
+        assert_eq!(&bytes[8..12], b"WAVE", "{}", event.path());
+    }
+}
@@ -0,0 +1,77 @@
+use maplit::btreeset;
+
+use chinese_checkers::board::{gen_teams, Board, HexCoord, Player, SideOfStar, Spot};
+
+#[test]
+fn partners_sit_next_to_the_opposite_side() {
+    use SideOfStar::*;
+    assert_eq!(
+        gen_teams(&btreeset![A, B, D, E]),
+        vec![btreeset![A, E], btreeset![B, D]]
+    );
+    assert_eq!(
+        gen_teams(&btreeset![A, B, C, D, E, F]),
+        vec![btreeset![A, C, E], btreeset![B, D, F]]
+    );
+    // with fewer players everyone plays alone
+    assert_eq!(
+        gen_teams(&btreeset![A, C, E]),
+        vec![btreeset![A], btreeset![C], btreeset![E]]
+    );
+}
+
+/// Fills all of `player`'s goal but the hole nearest the center, and puts a
+/// piece next to that hole. Returns the move that finishes.
+fn almost_finish(board: &mut Board, player: Player) -> (HexCoord, HexCoord) {
+    let center = HexCoord::new(0, 0);
+    let goal = board.goal(player);
+    let last = *goal
+        .iter()
+        .min_by_key(|coord| coord.distance(center))
+        .unwrap();
+    for &coord in &goal {
+        if coord != last {
+            board.put_player(coord, player);
+        }
+    }
+    let start = board
+        .iter()
+        .map(|(coord, _)| coord)
+        .find(|&coord| {
+            coord.distance(last) == 1
+                && !goal.contains(&coord)
+                && board.get(&coord) == Some(&Spot::Empty)
+        })
+        .unwrap();
+    board.put_player(start, player);
+    (start, last)
+}
+
+#[test]
+fn teams_win_once_every_partner_has_finished() {
+    let mut board = Board::new(4).unwrap();
+    board.team_play = true;
+    board.setup_players().unwrap();
+    assert_eq!(board.teams.len(), 2);
+
+    board.clear_pieces();
+    let (a_start, a_end) = almost_finish(&mut board, Player::A);
+    let (e_start, e_end) = almost_finish(&mut board, Player::E);
+    board.put_player(HexCoord::new(0, 0), Player::B);
+    board.put_player(HexCoord::new(1, 0), Player::D);
+
+    board.turn = Player::A;
+    board.make_move(a_start, a_end);
+    assert!(board.outcome.has_finished(Player::A));
+    assert!(board.outcome.team_ranking.is_empty());
+    assert!(!board.is_over());
+
+    board.turn = Player::E;
+    board.make_move(e_start, e_end);
+    assert!(board.outcome.has_finished(Player::E));
+    assert_eq!(
+        board.outcome.winning_team(),
+        Some(&btreeset![Player::A, Player::E])
+    );
+    assert!(board.is_over());
+}
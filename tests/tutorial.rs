@@ -0,0 +1,57 @@
+use chinese_checkers::board::{Board, SideOfStar};
+use chinese_checkers::tutorial::{lessons, STUDENT};
+
+fn longest_jump(board: &Board, side: SideOfStar) -> usize {
+    let mut board = board.clone();
+    board.turn = side;
+    board
+        .legal_moves()
+        .into_iter()
+        .map(|(start, end)| board.jump_count(start, end))
+        .max()
+        .unwrap_or(0)
+}
+
+#[test]
+fn every_task_can_be_done() {
+    for lesson in lessons() {
+        assert!(lesson
+            .highlights
+            .iter()
+            .all(|coord| lesson.board.get(coord).is_some()));
+        if let Some((start, end)) = lesson.task {
+            assert_eq!(lesson.board.turn, STUDENT, "{}", lesson.title);
+            assert!(lesson.board.validate_move(start, end), "{}", lesson.title);
+            assert!(lesson.accepts(start, end));
+            assert!(lesson.highlights.contains(&end));
+        }
+    }
+}
+
+#[test]
+fn lessons_show_what_they_teach() {
+    let lessons = lessons();
+    let task = |title: &str| {
+        let lesson = lessons.iter().find(|lesson| lesson.title == title).unwrap();
+        let (start, end) = lesson.task.unwrap();
+        let mut after = lesson.board.clone();
+        after.make_move(start, end);
+        (
+            lesson.board.jump_count(start, end),
+            lesson.board.clone(),
+            after,
+        )
+    };
+
+    assert_eq!(task("Steps").0, 0);
+    assert_eq!(task("Jumps").0, 1);
+    assert_eq!(task("Chain jumps").0, 4);
+
+    let (_, _, finished) = task("Filling the goal");
+    assert!(finished.is_over());
+    assert_eq!(finished.outcome.winner(), Some(STUDENT));
+
+    let (_, before, after) = task("Blocking");
+    assert_eq!(longest_jump(&before, SideOfStar::D), 3);
+    assert_eq!(longest_jump(&after, SideOfStar::D), 1);
+}